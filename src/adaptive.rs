@@ -0,0 +1,168 @@
+use std::collections::VecDeque;
+
+const WINDOW: usize = 5; // number of recent foods the performance is judged on
+const FAST_TICKS: f32 = 15.0; // average ticks per food below which the game speeds up
+const SLOW_TICKS: f32 = 40.0; // average ticks per food above which the game slows down
+const STEP: f32 = 0.05;
+const COLLISION_STEP: f32 = 0.15;
+const MIN_FACTOR: f32 = 0.6;
+const MAX_FACTOR: f32 = 1.4;
+
+#[derive(Debug)]
+/// Represents the model behind the adaptive (rubber-band) difficulty.
+///
+/// The `AdaptiveDifficulty` watches how quickly the player reaches the food and how often they run
+/// into something, and turns that into a factor the tick interval is multiplied by: below `1.0` when
+/// the player is doing well, above `1.0` when they're struggling. The same factor scales how often the
+/// bug, coins and power-ups show up, so a struggling player gets more of them. The factor moves in small
+/// steps and stays between `MIN_FACTOR` and `MAX_FACTOR`, so the adjustment is hard to notice.
+///
+/// # Fields
+/// - `recent`: The number of ticks it took to reach each of the last `WINDOW` foods.
+/// - `ticks_since_food`: The number of ticks since the last food was eaten.
+/// - `stalled`: Whether the snake is currently stuck in front of an obstacle.
+/// - `factor`: The factor the tick interval is multiplied by.
+///
+/// # Methods
+/// ## `new`
+/// Creates a new `AdaptiveDifficulty` that doesn't change the interval yet.
+///
+/// ## `tick`
+/// Records that the snake moved by one cell.
+///
+/// ## `food_eaten`
+/// Records that the snake reached the food, adjusting the factor to the recent pace.
+///
+/// ## `collided`
+/// Records that the snake ran into an obstacle, which eases the game. A snake that stays stuck in
+/// front of the same obstacle only counts once.
+///
+/// ## `restart`
+/// Starts counting for a new game, keeping the factor and the recent pace the player showed in the
+/// games before, so a restart doesn't throw away what the model learned.
+///
+/// ## `get_factor`
+/// Returns the factor the tick interval and the spawn rates should be multiplied by.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::adaptive::AdaptiveDifficulty;
+/// # use std::time::Duration;
+/// let base_interval = Duration::from_millis(200);
+/// let mut difficulty = AdaptiveDifficulty::new();
+/// difficulty.tick();
+/// difficulty.food_eaten();
+/// let interval = base_interval.mul_f32(difficulty.get_factor());
+/// ```
+pub struct AdaptiveDifficulty {
+    recent: VecDeque<u32>,
+    ticks_since_food: u32,
+    stalled: bool,
+    factor: f32
+}
+
+impl AdaptiveDifficulty {
+    pub fn new() -> Self {
+        Self { recent: VecDeque::with_capacity(WINDOW), ticks_since_food: 0, stalled: false, factor: 1.0 }
+    }
+
+    pub fn tick(&mut self) {
+        self.ticks_since_food += 1;
+        self.stalled = false;
+    }
+
+    pub fn food_eaten(&mut self) {
+        if self.recent.len() == WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(self.ticks_since_food);
+        self.ticks_since_food = 0;
+
+        let average = self.recent.iter().sum::<u32>() as f32 / self.recent.len() as f32;
+        if average < FAST_TICKS {
+            self.adjust(-STEP);
+        } else if average > SLOW_TICKS {
+            self.adjust(STEP);
+        }
+    }
+
+    pub fn collided(&mut self) {
+        if !self.stalled {
+            self.stalled = true;
+            self.adjust(COLLISION_STEP);
+        }
+    }
+
+    pub fn restart(&mut self) {
+        self.ticks_since_food = 0;
+        self.stalled = false;
+    }
+
+    pub fn get_factor(&self) -> f32 {
+        self.factor
+    }
+
+    fn adjust(&mut self, step: f32) {
+        self.factor = (self.factor + step).clamp(MIN_FACTOR, MAX_FACTOR);
+    }
+}
+
+impl Default for AdaptiveDifficulty {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // reaches the food after the given number of ticks
+    fn eat_after(difficulty: &mut AdaptiveDifficulty, ticks: u32) {
+        for _ in 0..ticks {
+            difficulty.tick();
+        }
+        difficulty.food_eaten();
+    }
+
+    #[test]
+    fn steps_with_the_pace_of_the_recent_foods() {
+        let mut difficulty = AdaptiveDifficulty::new();
+        eat_after(&mut difficulty, 10);
+        assert_eq!(difficulty.get_factor(), 1.0 - STEP);
+
+        // the average of 10 and 50 ticks is neither fast nor slow
+        eat_after(&mut difficulty, 50);
+        assert_eq!(difficulty.get_factor(), 1.0 - STEP);
+
+        eat_after(&mut difficulty, 100);
+        assert_eq!(difficulty.get_factor(), 1.0);
+    }
+
+    #[test]
+    fn stays_within_its_bounds() {
+        let mut difficulty = AdaptiveDifficulty::new();
+        for _ in 0..50 {
+            eat_after(&mut difficulty, 1);
+        }
+        assert_eq!(difficulty.get_factor(), MIN_FACTOR);
+
+        for _ in 0..50 {
+            difficulty.tick();
+            difficulty.collided();
+        }
+        assert_eq!(difficulty.get_factor(), MAX_FACTOR);
+    }
+
+    #[test]
+    fn counts_a_stalled_snake_once() {
+        let mut difficulty = AdaptiveDifficulty::new();
+        difficulty.collided();
+        difficulty.collided();
+        assert_eq!(difficulty.get_factor(), 1.0 + COLLISION_STEP);
+
+        difficulty.tick();
+        difficulty.collided();
+        assert_eq!(difficulty.get_factor(), 1.0 + 2.0 * COLLISION_STEP);
+    }
+}
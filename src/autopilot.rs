@@ -0,0 +1,119 @@
+use crate::direction::Direction;
+use crate::engine::{GameEngine, Player};
+use crate::pathfinding::{self, DIRECTIONS};
+use crate::points::Point;
+
+/// Decides which way the built-in autopilot steers the first snake on the next tick.
+///
+/// The autopilot follows the shortest path to the normal food, around obstacles and the snakes. It
+/// only takes that path if the snake still has at least as many free cells around it as it's long
+/// after the first move, so it doesn't chase food into a dead end. Otherwise, or when the food can't
+/// be reached, it moves towards the largest free area to stall until a path opens up. When the snake
+/// wraps around the edges of the board, so do the paths and the free areas.
+///
+/// # Parameters
+/// - `engine`: The `GameEngine` the snake is playing in.
+///
+/// # Returns
+/// `Some(Direction)` to steer in, which may be the way the snake is already heading, or `None` if
+/// every move leads into a collision.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::autopilot::choose_turn;
+/// # use snake_the_sequel::command::Command;
+/// # use snake_the_sequel::engine::{GameEngine, GameStatus};
+/// let mut engine = GameEngine::new(30, 10);
+/// while engine.step(choose_turn(&engine).map(Command::Turn)) == GameStatus::Running {}
+/// println!("The autopilot scored {}", engine.get_score());
+/// ```
+pub fn choose_turn(engine: &GameEngine) -> Option<Direction> {
+    let snake = engine.get_snake();
+    let head = snake.get_head_point();
+    let (width, height, wrap_around) = (engine.get_width(), engine.get_height(), engine.is_wrap_around());
+
+    let blocked = |point: &Point| is_blocked(engine, point);
+    let space = |point: &Point| pathfinding::count_reachable(width, height, *point, wrap_around, blocked);
+
+    let moves: Vec<(Direction, Point)> = DIRECTIONS.iter()
+        .filter(|&&direction| direction != snake.get_direction().opposite())
+        .filter_map(|&direction| pathfinding::neighbour(head, direction, width, height, wrap_around).map(|point| (direction, point)))
+        .filter(|(_, point)| !blocked(point))
+        .collect();
+
+    let towards_food = engine.get_food()
+        .and_then(|food| pathfinding::shortest_path(width, height, head, food, wrap_around, blocked))
+        .and_then(|path| moves.iter().find(|(_, point)| *point == path[0]).copied())
+        .filter(|(_, point)| space(point) >= snake.len());
+
+    towards_food
+        .or_else(|| moves.iter().copied().max_by_key(|(_, point)| space(point)))
+        .map(|(direction, _)| direction)
+}
+
+// checks if a snake can't move onto a cell, because of an obstacle or a snake, apart from tails moving out of the way
+fn is_blocked(engine: &GameEngine, point: &Point) -> bool {
+    engine.is_obstacle(point) || engine.get_players().iter().map(Player::get_snake).any(|snake| {
+        let tail_leaves = !snake.is_digesting() && !engine.is_strict();
+        snake.contains_point(point) && !(tail_leaves && *point == snake.get_tail_point())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::RandomSource;
+
+    // always picks the lowest value, so the snake starts heading up from the middle of the board
+    #[derive(Debug)]
+    struct LowestRandom;
+
+    impl RandomSource for LowestRandom {
+        fn gen_range(&mut self, low: u16, _high: u16) -> u16 {
+            low
+        }
+    }
+
+    // an engine on a 10x10 board with the snake's head at (5, 5), heading up, and the food at the given cell
+    fn engine_with_food(food: Point) -> GameEngine {
+        let mut engine = GameEngine::with_random(10, 10, Box::new(LowestRandom));
+        engine.set_food(food);
+        engine
+    }
+
+    #[test]
+    fn heads_for_the_food() {
+        let engine = engine_with_food(Point::new(8, 5));
+
+        assert_eq!(engine.get_snake().get_direction(), Direction::Up);
+        assert_eq!(choose_turn(&engine), Some(Direction::Right));
+    }
+
+    #[test]
+    fn takes_the_way_around_the_edges_when_wrapping_around() {
+        let mut engine = engine_with_food(Point::new(5, 8));
+        assert!(engine.teleport(Point::new(5, 1)));
+
+        assert_ne!(choose_turn(&engine), Some(Direction::Up));
+        engine.set_wrap_around(true);
+        assert_eq!(choose_turn(&engine), Some(Direction::Up));
+    }
+
+    #[test]
+    fn leaves_food_in_a_dead_end() {
+        let mut engine = engine_with_food(Point::new(0, 0));
+        // a pocket of two cells in front of the snake, which is three cells long
+        engine.set_obstacles(vec![Point::new(4, 4), Point::new(6, 4), Point::new(4, 3), Point::new(6, 3), Point::new(5, 2)]);
+        engine.set_food(Point::new(5, 3));
+
+        assert!(matches!(choose_turn(&engine), Some(Direction::Left | Direction::Right)));
+    }
+
+    #[test]
+    fn gives_up_when_boxed_in() {
+        let mut engine = engine_with_food(Point::new(0, 0));
+        engine.set_obstacles(vec![Point::new(5, 4), Point::new(4, 5), Point::new(6, 5)]);
+
+        assert_eq!(choose_turn(&engine), None);
+    }
+}
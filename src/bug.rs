@@ -0,0 +1,134 @@
+use crate::direction::Direction;
+use crate::points::Point;
+use crate::random::RandomSource;
+
+const BUG_LENGTH: u16 = 2;
+const BUG_LIFETIME: u16 = 40; // ticks before the bug crawls off again
+const BUG_CRAWL_PERIOD: u16 = 2; // the bug moves once every this many ticks
+const BUG_MAX_POINTS: u16 = 10;
+
+#[derive(Debug)]
+/// Represents a bonus bug crawling along an edge of the board.
+///
+/// A `Bug` occupies a few cells next to each other along one of the edges of the grid and crawls
+/// back and forth along that edge until its lifetime runs out. Catching it early is worth more
+/// points than catching it just before it disappears.
+///
+/// # Fields
+/// - `cells`: The cells occupied by the bug, the first one being its head.
+/// - `direction`: The direction the bug is crawling in.
+/// - `width`: The width of the game grid.
+/// - `height`: The height of the game grid.
+/// - `age`: The number of ticks since the bug appeared.
+///
+/// # Methods
+/// ## `spawn`
+/// Creates a new `Bug` at a random position along a random edge of the grid.
+///
+/// ### Parameters
+/// - `width`: The width of the game grid.
+/// - `height`: The height of the game grid.
+/// - `random`: The `RandomSource` used to pick the edge and position.
+///
+/// ### Returns
+/// A new instance of the `Bug`.
+///
+/// ## `crawl`
+/// Advances the bug by one tick, moving it along its edge every `BUG_CRAWL_PERIOD` ticks and turning
+/// it around at the corners.
+///
+/// ## `contains_point`
+/// Checks if the bug occupies a specific point.
+///
+/// ### Parameters
+/// - `point`: A reference to a `Point` to check.
+///
+/// ### Returns
+/// `true` if the point is part of the bug, otherwise `false`.
+///
+/// ## `get_points`
+/// Returns the cells occupied by the bug.
+///
+/// ## `is_expired`
+/// Checks if the bug's lifetime has run out.
+///
+/// ## `get_reward`
+/// Returns the number of points the bug is worth if it were eaten now, which decreases with its age.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::bug::Bug;
+/// # use snake_the_sequel::random::ThreadRandom;
+/// let mut bug = Bug::spawn(30, 10, &mut ThreadRandom);
+/// bug.crawl();
+/// let reward = bug.get_reward();
+/// ```
+pub struct Bug {
+    cells: Vec<Point>,
+    direction: Direction,
+    width: u16,
+    height: u16,
+    age: u16
+}
+
+impl Bug {
+    pub fn spawn(width: u16, height: u16, random: &mut dyn RandomSource) -> Self {
+        // the bug crawls along the edge, its head leading and the rest of it trailing behind
+        let (start, direction) = match random.gen_range(0, 4) {
+            0 => (Point::new(random.gen_range(BUG_LENGTH - 1, width), 0), Direction::Right),
+            1 => (Point::new(width - 1, random.gen_range(BUG_LENGTH - 1, height)), Direction::Down),
+            2 => (Point::new(random.gen_range(0, width - BUG_LENGTH + 1), height - 1), Direction::Left),
+            3 => (Point::new(0, random.gen_range(0, height - BUG_LENGTH + 1)), Direction::Up),
+            _ => unreachable!()
+        };
+
+        let cells = (0..BUG_LENGTH)
+            .map(|i| start.transform(direction.opposite(), i))
+            .collect();
+
+        Self { cells, direction, width, height, age: 0 }
+    }
+
+    pub fn crawl(&mut self) {
+        self.age += 1;
+        if !self.age.is_multiple_of(BUG_CRAWL_PERIOD) {
+            return;
+        }
+
+        if self.is_at_end() {
+            self.cells.reverse();
+            self.direction = self.direction.opposite();
+        }
+
+        self.cells.insert(0, self.cells[0].transform(self.direction, 1));
+        self.cells.pop();
+    }
+
+    pub fn contains_point(&self, point: &Point) -> bool {
+        self.cells.contains(point)
+    }
+
+    pub fn get_points(&self) -> &[Point] {
+        &self.cells
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.age >= BUG_LIFETIME
+    }
+
+    pub fn get_reward(&self) -> u16 {
+        let remaining = BUG_LIFETIME.saturating_sub(self.age);
+        1 + (BUG_MAX_POINTS - 1) * remaining / BUG_LIFETIME
+    }
+
+    fn is_at_end(&self) -> bool {
+        let head = self.cells[0];
+
+        match self.direction {
+            Direction::Up => head.y == 0,
+            Direction::Right => head.x == self.width - 1,
+            Direction::Down => head.y == self.height - 1,
+            Direction::Left => head.x == 0
+        }
+    }
+}
@@ -0,0 +1,398 @@
+use snake_the_sequel::config::{self, Config};
+use snake_the_sequel::controls::{ControlScheme, DirectionMap, Layout};
+use snake_the_sequel::effects::Ambience;
+use snake_the_sequel::levels::Level;
+use snake_the_sequel::modes::GameMode;
+use snake_the_sequel::speed::{Difficulty, SpeedCurve};
+use snake_the_sequel::theme::Theme;
+use snake_the_sequel::engine::{MAX_BOARD_SIZE, MAX_SPEED, MIN_BOARD_SIZE};
+use snake_the_sequel::keymap;
+
+use clap::{Arg, ArgAction, Command, ValueHint};
+use clap_complete::Shell;
+use std::io::Write;
+use std::path::Path;
+
+pub const USAGE: &str = "\
+Usage: snake_the_sequel [options]
+       snake_the_sequel completions <shell>
+       snake_the_sequel man
+
+Every option can also be set in config.toml in the user's config directory
+(e.g. ~/.config/snake-cli-rs/config.toml), with the options overriding it.
+
+Board:
+  --width <5-200>          Width of the board in cells (default 30)
+  --height <5-200>         Height of the board in cells (default 10)
+  --speed <1-20>           Speed the game starts at (default 1)
+  --fixed-speed <1-20>     Lock the speed for the whole run
+  --difficulty <preset>    easy, normal, hard or insane (default normal)
+  --speed-curve <s,a,f>    Custom curve: ms per tick at speed 1, ms faster per level, minimum ms
+  --no-walls               Wrap around to the other side instead of crashing into the walls
+  --level <level>          open, arena, cross, pillars or maze (default open)
+
+Modes:
+  --mode <mode>            classic, time-attack (2 minutes) or survival (faster every 10 seconds)
+  --practice               Collisions aren't fatal and +/- change the speed
+  --strict                 Forbid moving onto the cell the tail is leaving
+  --hardcore               Make the score decay over time
+  --god                    Enable god mode, with the developer console on the backtick
+  --endless                Add a random challenge rule every 25 points
+  --adaptive               Adjust the speed and the bonuses to how well the player is doing
+  --bounce                 Turn along walls the snake runs into, for a point
+  --players <1-2>          Two players on one keyboard, with the arrow keys and WASD (default 1)
+  --demo                   Let the autopilot play, as an attract mode
+  --fresh-food             Make food worth more the sooner it's eaten
+  --limited-vision         Only show the cells around and ahead of the snake
+
+Controls:
+  --controls <scheme>      absolute, relative, left-hand, right-hand or vim
+  --layout <layout>        qwerty, azerty, dvorak or colemak
+  --keymap <file>          Read key bindings from a file, with lines like: pause = p, space
+  --bind <action=keys>     Bind keys to an action, e.g. --bind quit=x,esc (repeatable)
+  --mirror                 Swap the left and right controls
+  --mirror-all             Swap up and down as well
+  --shuffle-controls       Remap the direction keys every 20 seconds
+  --afk-timeout <seconds>  Pause after this long without input, 0 disables it (default 30)
+
+Display:
+  --theme <theme>          classic, neon or monochrome (default classic)
+  --ascii                  Draw with plain ASCII characters instead of box-drawing ones
+  --no-menu                Start playing straight away instead of showing the main menu
+  --ambient <effect>       rain or stars
+  --color-snake <color>    Color of the snake, e.g. green or dark-blue
+  --color-food <color>     Color of the food
+  --color-border <color>   Color of the borders and obstacles
+  --milestone-borders      Change the border color at length milestones
+  --coordinates            Number the rows and columns along the borders
+  --head-position          Show the position of the snake's head
+  --hints                  Mark the shortest path to the food in practice and god mode
+
+Output:
+  --profile                Print a histogram of the frame timings when the game ends
+  --screenshot-on-death    Save a PNG of the board when the snake dies
+  --export-svg             Save the final board as an SVG drawing
+  --seed <number>          Seed the food placement and other random events, to replay the same game
+  --record-macro           Play a seeded game and save its turns to a macro file
+  --macro <file>           Replay a recorded macro
+  --verbose                Write every log message to snake.log
+  --help                   Show this message
+
+Commands:
+  completions <shell>      Print a completion script for bash, elvish, fish, powershell or zsh
+  man                      Print a man page";
+
+#[derive(Debug)]
+/// Represents the options the game is started with, as given on the command line.
+///
+/// The settings of the game itself end up in a `Config`, on top of the settings it already holds from
+/// the config file, so a flag always wins over the file. Unknown arguments and invalid values are
+/// rejected with a message explaining what was expected, which `main` prints together with `USAGE`.
+///
+/// # Fields
+/// - `help`: Whether the usage message was asked for.
+/// - `generate`: The completion script or man page to print instead of playing, if any.
+/// - `profile`: Whether the frame timings are reported when the game ends.
+/// - `record_macro`: Whether the turns of the run are recorded to a macro file.
+/// - `macro_file`: The path of a macro to replay, if any.
+/// - `seed`: The seed of the game's random events, if a reproducible game was asked for.
+/// - `verbose`: Whether every log message is written to the log file.
+/// - `config`: The settings of the game.
+///
+/// # Methods
+/// ## `parse`
+/// Parses the command line arguments, including the name of the program as the first one.
+///
+/// ### Parameters
+/// - `args`: The arguments the game was started with.
+/// - `config`: The settings to apply the options on top of, usually read from the config file.
+///
+/// ### Returns
+/// The parsed `Options`, or a message describing the first invalid argument.
+///
+/// # Example
+/// ```rust
+/// let args: Vec<String> = std::env::args().collect();
+/// let options = Options::parse(&args, Config::default()).unwrap();
+/// let game = Game::new(stdout(), &options.config);
+/// ```
+pub struct Options {
+    pub help: bool,
+    pub generate: Option<Generate>,
+    pub profile: bool,
+    pub record_macro: bool,
+    pub macro_file: Option<String>,
+    pub seed: Option<u64>,
+    pub verbose: bool,
+    pub config: Config
+}
+
+impl Options {
+    pub fn parse(args: &[String], config: Config) -> Result<Self, String> {
+        let mut options = Self {
+            help: false,
+            generate: None,
+            profile: false,
+            record_macro: false,
+            macro_file: None,
+            seed: None,
+            verbose: false,
+            config
+        };
+
+        // the commands replace the options rather than adding to them
+        match args.get(1).map(String::as_str) {
+            Some("completions") => {
+                let shell = value("completions", args.get(2), "one of: bash, elvish, fish, powershell, zsh", |value| value.parse().ok())?;
+                options.generate = Some(Generate::Completions(shell));
+                return no_more_arguments(&args[3..]).map(|_| options);
+            }
+            Some("man") => {
+                options.generate = Some(Generate::Man);
+                return no_more_arguments(&args[2..]).map(|_| options);
+            }
+            _ => ()
+        }
+
+        // the first argument is the name of the program
+        let mut args = args.iter().skip(1);
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--help" | "-h" => options.help = true,
+                "--width" => options.config.width = board_size(arg, args.next())?,
+                "--height" => options.config.height = board_size(arg, args.next())?,
+                "--speed" => options.config.speed = value(arg, args.next(), "a speed from 1 to 20", parse_speed)?,
+                "--fixed-speed" => options.config.fixed_speed = Some(value(arg, args.next(), "a speed from 1 to 20", parse_speed)?),
+                "--difficulty" => options.config.speed_curve = value(arg, args.next(), "one of: easy, normal, hard, insane", Difficulty::from_name)?.curve(),
+                "--speed-curve" => options.config.speed_curve = value(arg, args.next(), "start, acceleration and floor in milliseconds, e.g. 675,25,200", SpeedCurve::parse)?,
+                "--no-walls" => options.config.wrap_around = true,
+                "--level" => options.config.level = value(arg, args.next(), "one of: open, arena, cross, pillars, maze", Level::from_name)?,
+                "--profile" => options.profile = true,
+                "--mode" => options.config.mode = value(arg, args.next(), "one of: classic, time-attack, survival", GameMode::from_name)?,
+                "--practice" => options.config.practice = true,
+                "--strict" => options.config.strict = true,
+                "--hardcore" => options.config.hardcore = true,
+                "--god" => options.config.god = true,
+                "--endless" => options.config.endless = true,
+                "--adaptive" => options.config.adaptive = true,
+                "--bounce" => options.config.bounce = true,
+                "--demo" => options.config.demo = true,
+                "--players" => options.config.players = value(arg, args.next(), "1 or 2 players", |value| value.parse().ok().filter(|players| (1..=2).contains(players)))?,
+                "--fresh-food" => options.config.fresh_food = true,
+                "--limited-vision" => options.config.limited_vision = true,
+                "--controls" => options.config.scheme = value(arg, args.next(), "one of: absolute, relative, left-hand, right-hand, vim", ControlScheme::from_name)?,
+                "--layout" => options.config.layout = value(arg, args.next(), "one of: qwerty, azerty, dvorak, colemak", Layout::from_name)?,
+                "--keymap" => {
+                    let path = value(arg, args.next(), "the path of a keymap file", |value| Some(String::from(value)))?;
+                    let bindings = keymap::load_bindings(Path::new(&path)).map_err(|error| format!("Failed to load keymap {}: {}", path, error))?;
+                    options.config.bindings.extend(bindings);
+                }
+                "--bind" => options.config.bindings.push(value(arg, args.next(), "a binding like quit=x,esc", keymap::parse_binding)?),
+                "--color-snake" => options.config.colors.snake = Some(value(arg, args.next(), "a color like green or dark-blue", config::parse_color)?),
+                "--color-food" => options.config.colors.food = Some(value(arg, args.next(), "a color like green or dark-blue", config::parse_color)?),
+                "--color-border" => options.config.colors.border = Some(value(arg, args.next(), "a color like green or dark-blue", config::parse_color)?),
+                "--mirror" => options.config.controls = DirectionMap::mirrored(false),
+                "--mirror-all" => options.config.controls = DirectionMap::mirrored(true),
+                "--shuffle-controls" => options.config.shuffle_controls = true,
+                "--afk-timeout" => options.config.afk_timeout = value(arg, args.next(), "a number of seconds", |value| value.parse().ok())?,
+                "--theme" => options.config.theme = value(arg, args.next(), "one of: classic, neon, monochrome", Theme::from_name)?,
+                "--ascii" => options.config.ascii = true,
+                "--no-menu" => options.config.menu = false,
+                "--ambient" => options.config.ambience = Some(value(arg, args.next(), "one of: rain, stars", Ambience::from_name)?),
+                "--milestone-borders" => options.config.milestone_borders = true,
+                "--coordinates" => options.config.coordinates = true,
+                "--head-position" => options.config.head_position = true,
+                "--hints" => options.config.hints = true,
+                "--screenshot-on-death" => options.config.screenshot_on_death = true,
+                "--export-svg" => options.config.export_svg = true,
+                "--record-macro" => options.record_macro = true,
+                "--macro" => options.macro_file = Some(value(arg, args.next(), "the path of a macro file", |value| Some(String::from(value)))?),
+                "--seed" => options.seed = Some(value(arg, args.next(), "a number", |value| value.parse().ok())?),
+                "--verbose" => options.verbose = true,
+                _ => return Err(format!("Unknown option {}", arg))
+            }
+        }
+
+        // macros only hold the turns of a single snake
+        if options.config.players > 1 && (options.record_macro || options.macro_file.is_some()) {
+            return Err(String::from("--players 2 can't be combined with --record-macro or --macro"));
+        }
+        if options.config.demo && options.macro_file.is_some() {
+            return Err(String::from("--demo can't be combined with --macro, as both steer the snake"));
+        }
+        // a macro is replayed with the seed it was recorded with
+        if options.seed.is_some() && options.macro_file.is_some() {
+            return Err(String::from("--seed can't be combined with --macro, which brings its own seed"));
+        }
+
+        Ok(options)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Represents what to print instead of playing, as asked for with the `completions` and `man` commands.
+///
+/// Both are generated from the `Command` that `command` builds out of `USAGE`, so they list the same
+/// options as `--help` without having to be kept up to date by hand.
+///
+/// # Variants
+/// - `Completions`: A completion script for the given shell.
+/// - `Man`: A man page in the roff format.
+///
+/// # Methods
+/// ## `print`
+/// Writes the completion script or man page.
+///
+/// ### Parameters
+/// - `output`: Where to write it, usually the standard output.
+///
+/// ### Returns
+/// An error if writing failed.
+///
+/// # Example
+/// ```rust
+/// Generate::Completions(Shell::Bash).print(&mut stdout())?;
+/// ```
+pub enum Generate {
+    Completions(Shell),
+    Man
+}
+
+impl Generate {
+    pub fn print(self, output: &mut impl Write) -> std::io::Result<()> {
+        let mut command = command();
+
+        match self {
+            Generate::Completions(shell) => {
+                clap_complete::generate(shell, &mut command, "snake_the_sequel", output);
+                Ok(())
+            }
+            Generate::Man => clap_mangen::Man::new(command).render(output)
+        }
+    }
+}
+
+/// Builds a `clap` description of the command line out of the option lines in `USAGE`.
+///
+/// The options themselves are still parsed by `Options::parse`; the description only serves to
+/// generate completion scripts and the man page, so `USAGE` stays the one place the options are listed.
+///
+/// # Returns
+/// The `Command` with every option and command in `USAGE`, grouped under the same headings.
+///
+/// # Example
+/// ```rust
+/// let mut command = command();
+/// clap_complete::generate(Shell::Zsh, &mut command, "snake_the_sequel", &mut stdout());
+/// ```
+pub fn command() -> Command {
+    let mut command = Command::new("snake_the_sequel")
+        .about("Snake in the terminal")
+        .version(env!("CARGO_PKG_VERSION"))
+        .disable_help_flag(true)
+        .disable_version_flag(true)
+        .subcommand(Command::new("completions")
+            .about("Print a completion script for bash, elvish, fish, powershell or zsh")
+            .arg(Arg::new("shell").required(true).value_parser(clap::value_parser!(Shell))))
+        .subcommand(Command::new("man").about("Print a man page"));
+    let mut heading = None;
+
+    for line in USAGE.lines() {
+        if let Some(option) = line.strip_prefix("  --") {
+            // an option line is the flag, optionally a value, and the help text after a run of spaces
+            let (usage, help) = option.split_once("  ").unwrap_or((option, ""));
+            let mut usage = usage.split(' ');
+            let name = usage.next().unwrap_or_default();
+            let mut arg = Arg::new(name).long(name).help(help.trim_start()).help_heading(heading);
+
+            arg = match usage.next() {
+                Some(value) => {
+                    let value = value.trim_matches(|c| c == '<' || c == '>');
+                    let hint = if value == "file" { ValueHint::FilePath } else { ValueHint::Other };
+                    let action = if name == "bind" { ArgAction::Append } else { ArgAction::Set };
+                    arg.value_name(value).value_hint(hint).action(action)
+                }
+                None => arg.action(ArgAction::SetTrue)
+            };
+            if name == "help" {
+                arg = arg.short('h').action(ArgAction::Help);
+            }
+            command = command.arg(arg);
+        } else if !line.starts_with(' ') && line.ends_with(':') {
+            heading = Some(line.trim_end_matches(':'));
+        }
+    }
+
+    command
+}
+
+// fails if anything follows a command, which doesn't take any options
+fn no_more_arguments(args: &[String]) -> Result<(), String> {
+    match args.first() {
+        Some(arg) => Err(format!("Unexpected argument {}", arg)),
+        None => Ok(())
+    }
+}
+
+// parses the value following an option, failing with a message if it's missing or not one of the expected values
+fn value<T>(option: &str, value: Option<&String>, expected: &str, parse: impl Fn(&str) -> Option<T>) -> Result<T, String> {
+    value.and_then(|value| parse(value)).ok_or_else(|| format!("{} expects {}", option, expected))
+}
+
+// parses the width or height of the board, which has to leave room for the snake to start in either direction
+fn board_size(option: &str, size: Option<&String>) -> Result<u16, String> {
+    let size = value(option, size, "a number of cells", |value| value.parse::<u16>().ok())?;
+
+    if size < MIN_BOARD_SIZE {
+        Err(format!("{} {} is too small for the snake, the board needs at least {} cells", option, size, MIN_BOARD_SIZE))
+    } else if size > MAX_BOARD_SIZE {
+        Err(format!("{} {} is too large, the board can have at most {} cells", option, size, MAX_BOARD_SIZE))
+    } else {
+        Ok(size)
+    }
+}
+
+fn parse_speed(value: &str) -> Option<u16> {
+    value.parse().ok().filter(|speed| (1..=MAX_SPEED).contains(speed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        std::iter::once("snake_the_sequel").chain(args.iter().copied()).map(String::from).collect()
+    }
+
+    #[test]
+    fn describes_every_option_in_the_usage() {
+        let command = command();
+        command.clone().debug_assert();
+
+        let options = USAGE.lines().filter(|line| line.starts_with("  --")).count();
+        assert_eq!(command.get_arguments().count(), options);
+        assert!(command.get_arguments().any(|arg| arg.get_long() == Some("speed-curve") && arg.get_help_heading() == Some("Board")));
+    }
+
+    #[test]
+    fn parses_the_commands() {
+        let options = Options::parse(&args(&["completions", "fish"]), Config::default()).unwrap();
+        assert_eq!(options.generate, Some(Generate::Completions(Shell::Fish)));
+        assert_eq!(Options::parse(&args(&["man"]), Config::default()).unwrap().generate, Some(Generate::Man));
+
+        assert!(Options::parse(&args(&["completions", "cmd"]), Config::default()).is_err());
+        assert!(Options::parse(&args(&["man", "--width", "20"]), Config::default()).is_err());
+    }
+
+    #[test]
+    fn generates_completions_and_a_man_page() {
+        let mut output = Vec::new();
+        Generate::Completions(Shell::Bash).print(&mut output).unwrap();
+        assert!(String::from_utf8(output).unwrap().contains("--no-walls"));
+
+        let mut output = Vec::new();
+        Generate::Man.print(&mut output).unwrap();
+        assert!(String::from_utf8(output).unwrap().contains("no\\-walls"));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::direction::Direction;
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 /// Represents a game command.
 ///
 /// The `Command` enum defines actions that can be issued during gameplay, such as quitting the game 
@@ -9,21 +10,76 @@ use crate::direction::Direction;
 /// ## `Quit`
 /// Represents a command to quit the game.
 ///
+/// ## `Pause`
+/// Represents a command to freeze the game until the next key press.
+///
 /// ## `Turn`
 /// Represents a command to change the direction of the snake.
 ///
 /// ### Fields
 /// - `Direction`: The direction to which the snake should turn.
 ///
+/// ## `TurnPlayerTwo`
+/// Represents a command to change the direction of the second player's snake (two player games only).
+///
+/// ### Fields
+/// - `Direction`: The direction to which the snake should turn.
+///
+/// ## `SpawnFood`
+/// Represents a command to place food right in front of the snake (god mode only).
+///
+/// ## `Grow`
+/// Represents a command to grow the snake by one segment (god mode only).
+///
+/// ## `Shrink`
+/// Represents a command to remove the last segment of the snake (god mode only).
+///
+/// ## `Screenshot`
+/// Represents a command to save a PNG screenshot of the board.
+///
+/// ## `Console`
+/// Represents a command to open the developer console (god mode only).
+///
+/// ## `ToggleCoordinates`
+/// Represents a command to show or hide the debug coordinate overlay.
+///
+/// ## `ToggleHeadPosition`
+/// Represents a command to show or hide the position of the snake's head.
+///
+/// ## `SpeedUp`
+/// Represents a command to raise the speed by one level (practice and god mode only).
+///
+/// ## `SlowDown`
+/// Represents a command to lower the speed by one level (practice and god mode only).
+///
+/// ## `Resize`
+/// Represents the terminal being resized, after which the board is redrawn to fit it.
+///
+/// ### Fields
+/// - `u16`: The new number of columns of the terminal.
+/// - `u16`: The new number of rows of the terminal.
+///
 /// # Example
 /// ```rust
-/// use crate::direction::Direction;
-/// use crate::command::Command;
+/// use snake_the_sequel::direction::Direction;
+/// use snake_the_sequel::command::Command;
 ///
 /// let quit_command = Command::Quit;
 /// let turn_command = Command::Turn(Direction::Up);
 /// ```
 pub enum Command {
     Quit,
-    Turn(Direction)
+    Pause,
+    Turn(Direction),
+    TurnPlayerTwo(Direction),
+    SpawnFood,
+    Grow,
+    Shrink,
+    Screenshot,
+    Console,
+    ToggleCoordinates,
+    ToggleHeadPosition,
+    SpeedUp,
+    SlowDown,
+    Resize(u16, u16)
 }
\ No newline at end of file
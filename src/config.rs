@@ -0,0 +1,590 @@
+use crate::controls::{ControlScheme, DirectionMap, Layout};
+use crate::effects::Ambience;
+use crate::engine::{MAX_BOARD_SIZE, MAX_SPEED, MIN_BOARD_SIZE};
+use crate::keymap::{self, Action, Keymap};
+use crate::levels::Level;
+use crate::modes::GameMode;
+use crate::speed::{Difficulty, SpeedCurve};
+use crate::theme::Theme;
+
+use std::env;
+use std::fs;
+use std::io;
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+use std::str::Chars;
+use crossterm::event::KeyCode;
+use crossterm::style::Color;
+
+const DEFAULT_WIDTH: u16 = 30;
+const DEFAULT_HEIGHT: u16 = 10;
+const DEFAULT_AFK_TIMEOUT: u64 = 30; // seconds without input before the game pauses itself
+const CONFIG_DIR: &str = "snake-cli-rs";
+const FILE_NAME: &str = "config.toml";
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+/// Represents the colors the player chose for the board, each replacing the game's own coloring.
+///
+/// # Fields
+/// - `snake`: The color of the first snake, instead of changing with the speed.
+/// - `food`: The color of the normal food, which still pulses.
+/// - `border`: The color of the borders and obstacles, instead of changing with the milestones.
+pub struct Colors {
+    pub snake: Option<Color>,
+    pub food: Option<Color>,
+    pub border: Option<Color>
+}
+
+#[derive(Debug, Clone)]
+/// Represents every setting of a game, from the size of the board to the keys.
+///
+/// A `Config` starts out with the defaults of a regular game on a 30 by 10 board. The settings in the
+/// config file are applied on top of them, and the command line options on top of those, so a flag
+/// always wins over the file.
+///
+/// The config file is a small subset of TOML: `[section]` headers followed by `key = value` lines, where
+/// a value is a quoted string, a whole number, `true` or `false`, or a list of quoted strings, and `#`
+/// starts a comment. Quoted strings may contain `\"`, `\\`, `\n` and `\t` escapes. The keys are named after
+/// the command line options:
+///
+/// ```toml
+/// [board]
+/// width = 40
+/// height = 15
+/// level = "maze"
+/// wrap-around = false
+///
+/// [speed]
+/// start = 10
+/// fixed = 10
+/// difficulty = "hard"   # or a curve = "675,25,200" of its own, in milliseconds
+///
+/// [mode]
+/// mode = "survival"  # or "time-attack", or "classic"
+/// practice = true    # also strict, hardcore, god, endless, adaptive, bounce, fresh-food, limited-vision and demo
+/// players = 2
+///
+/// [controls]
+/// scheme = "vim"
+/// layout = "qwerty"
+/// mirror = "all"     # or "left-right", or "none"
+/// shuffle = false
+/// afk-timeout = 30
+///
+/// [display]
+/// theme = "neon"     # or "classic", or "monochrome"
+/// ambient = "stars"  # also ascii, milestone-borders, coordinates, head-position and hints
+/// menu = false       # start playing straight away
+///
+/// [output]
+/// screenshot-on-death = true    # also export-svg
+///
+/// [colors]
+/// snake = "cyan"
+/// food = "yellow"
+/// border = "dark-blue"
+///
+/// [keys]
+/// quit = ["x", "esc"]
+/// pause = "p"
+/// ```
+///
+/// # Fields
+/// - `width`: The width of the board, in cells.
+/// - `height`: The height of the board, in cells.
+/// - `speed`: The speed the game starts at, from 1 (slowest) to `MAX_SPEED`.
+/// - `fixed_speed`: The speed to lock the game at, if any.
+/// - `speed_curve`: How the time between ticks shrinks as the speed goes up.
+/// - `wrap_around`: Whether the snake wraps around to the other side of the board instead of hitting a wall.
+/// - `level`: The layout of the obstacles on the board.
+/// - `mode`: The `GameMode` deciding how the game speeds up, scores and ends.
+/// - `practice`: Whether the game runs in practice mode.
+/// - `strict`: Whether the snake may not move onto the cell its tail is leaving.
+/// - `hardcore`: Whether the score decays over time.
+/// - `god`: Whether god mode is enabled.
+/// - `endless`: Whether challenge rules are added as the score increases.
+/// - `adaptive`: Whether the speed adapts to the player's performance.
+/// - `bounce`: Whether the snake bounces off walls instead of crashing.
+/// - `players`: The number of players sharing the keyboard, 1 or 2.
+/// - `demo`: Whether the autopilot plays instead of the player.
+/// - `fresh_food`: Whether food is worth more the sooner it's eaten.
+/// - `limited_vision`: Whether only the cells around the snake's head are rendered.
+/// - `scheme`: The keys used to steer the snake.
+/// - `layout`: The keyboard layout of the player.
+/// - `bindings`: The keys bound to actions on top of the defaults, in the order they're applied.
+/// - `controls`: The remapping of the direction keys.
+/// - `shuffle_controls`: Whether the direction keys are remapped at random intervals.
+/// - `afk_timeout`: The number of seconds without input before the game pauses itself, or 0 to never pause.
+/// - `theme`: The colors and characters the board is drawn with.
+/// - `ascii`: Whether the board is drawn with plain ASCII characters only.
+/// - `menu`: Whether the main menu is shown before the game.
+/// - `ambience`: The ambient effect rendered behind the board, if any.
+/// - `milestone_borders`: Whether the border color changes at length milestones.
+/// - `coordinates`: Whether the rows and columns are numbered along the borders.
+/// - `head_position`: Whether the position of the snake's head is shown.
+/// - `hints`: Whether the shortest path to the food is shown.
+/// - `screenshot_on_death`: Whether a PNG of the board is saved when the snake dies.
+/// - `export_svg`: Whether the final board is exported as an SVG drawing.
+/// - `colors`: The colors chosen for the board.
+///
+/// # Methods
+/// ## `default`
+/// Creates a `Config` with the settings of a regular game.
+///
+/// ## `load`
+/// Applies the settings in a config file on top of the current ones.
+///
+/// ### Parameters
+/// - `path`: The path of the config file.
+///
+/// ### Returns
+/// An `io::Result` indicating whether the file could be read and every setting in it is valid.
+///
+/// ## `set`
+/// Applies a single setting.
+///
+/// ### Parameters
+/// - `section`: The section the setting is in, such as `board`.
+/// - `key`: The name of the setting, such as `width`.
+/// - `value`: The value of the setting.
+///
+/// ### Returns
+/// `Ok(())` if the setting was applied, otherwise a message describing what's wrong with it.
+///
+/// ## `keymap`
+/// Builds the `Keymap` of the control scheme and layout, with the bindings applied on top.
+///
+/// # Example
+/// ```no_run
+/// # use std::io::stdout;
+/// # use snake_the_sequel::config::{self, Config};
+/// # use snake_the_sequel::game::Game;
+/// let mut config = Config::default();
+/// if let Some(path) = config::default_path().filter(|path| path.exists()) {
+///     config.load(&path).unwrap();
+/// }
+/// let game = Game::new(stdout(), &config);
+/// ```
+pub struct Config {
+    pub width: u16,
+    pub height: u16,
+    pub speed: u16,
+    pub fixed_speed: Option<u16>,
+    pub speed_curve: SpeedCurve,
+    pub wrap_around: bool,
+    pub level: Level,
+    pub mode: GameMode,
+    pub practice: bool,
+    pub strict: bool,
+    pub hardcore: bool,
+    pub god: bool,
+    pub endless: bool,
+    pub adaptive: bool,
+    pub bounce: bool,
+    pub players: u16,
+    pub demo: bool,
+    pub fresh_food: bool,
+    pub limited_vision: bool,
+    pub scheme: ControlScheme,
+    pub layout: Layout,
+    pub bindings: Vec<(Action, Vec<KeyCode>)>,
+    pub controls: DirectionMap,
+    pub shuffle_controls: bool,
+    pub afk_timeout: u64,
+    pub theme: Theme,
+    pub ascii: bool,
+    pub menu: bool,
+    pub ambience: Option<Ambience>,
+    pub milestone_borders: bool,
+    pub coordinates: bool,
+    pub head_position: bool,
+    pub hints: bool,
+    pub screenshot_on_death: bool,
+    pub export_svg: bool,
+    pub colors: Colors
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// Represents a value in the config file.
+///
+/// # Variants
+/// - `Text`: A quoted string.
+/// - `Number`: A whole number.
+/// - `Flag`: `true` or `false`.
+/// - `List`: A list of quoted strings, such as the keys of a binding.
+pub enum Value {
+    Text(String),
+    Number(u64),
+    Flag(bool),
+    List(Vec<String>)
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            width: DEFAULT_WIDTH,
+            height: DEFAULT_HEIGHT,
+            speed: 1,
+            fixed_speed: None,
+            speed_curve: SpeedCurve::default(),
+            wrap_around: false,
+            level: Level::Open,
+            mode: GameMode::default(),
+            practice: false,
+            strict: false,
+            hardcore: false,
+            god: false,
+            endless: false,
+            adaptive: false,
+            bounce: false,
+            players: 1,
+            demo: false,
+            fresh_food: false,
+            limited_vision: false,
+            scheme: ControlScheme::Absolute,
+            layout: Layout::Qwerty,
+            bindings: Vec::new(),
+            controls: DirectionMap::identity(),
+            shuffle_controls: false,
+            afk_timeout: DEFAULT_AFK_TIMEOUT,
+            theme: Theme::default(),
+            ascii: false,
+            menu: true,
+            ambience: None,
+            milestone_borders: false,
+            coordinates: false,
+            head_position: false,
+            hints: false,
+            screenshot_on_death: false,
+            export_svg: false,
+            colors: Colors::default()
+        }
+    }
+}
+
+impl Config {
+    pub fn load(&mut self, path: &Path) -> io::Result<()> {
+        let contents = fs::read_to_string(path)?;
+        let mut section = String::new();
+
+        for (i, line) in contents.lines().enumerate() {
+            let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", i + 1, message));
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[') {
+                let name = name.split('#').next().unwrap_or_default().trim();
+                section = match name.strip_suffix(']') {
+                    Some(name) => String::from(name.trim()),
+                    None => return Err(invalid(format!("invalid section header: {}", line)))
+                };
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or_else(|| invalid(format!("expected key = value: {}", line)))?;
+            let value = parse_value(value).ok_or_else(|| invalid(format!("invalid value: {}", line)))?;
+            self.set(&section, key.trim(), value).map_err(invalid)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn set(&mut self, section: &str, key: &str, value: Value) -> Result<(), String> {
+        let name = format!("{}.{}", section, key);
+
+        match (section, key) {
+            ("board", "width") => self.width = board_size(&name, value)?,
+            ("board", "height") => self.height = board_size(&name, value)?,
+            ("board", "level") => self.level = named(&name, value, "one of: open, arena, cross, pillars, maze", Level::from_name)?,
+            ("board", "wrap-around") => self.wrap_around = flag(&name, value)?,
+            ("speed", "start") => self.speed = speed(&name, value)?,
+            ("speed", "fixed") => self.fixed_speed = Some(speed(&name, value)?),
+            ("speed", "difficulty") => self.speed_curve = named(&name, value, "one of: easy, normal, hard, insane", Difficulty::from_name)?.curve(),
+            ("speed", "curve") => self.speed_curve = named(&name, value, "start, acceleration and floor in milliseconds", SpeedCurve::parse)?,
+            ("mode", "mode") => self.mode = named(&name, value, "one of: classic, time-attack, survival", GameMode::from_name)?,
+            ("mode", "practice") => self.practice = flag(&name, value)?,
+            ("mode", "strict") => self.strict = flag(&name, value)?,
+            ("mode", "hardcore") => self.hardcore = flag(&name, value)?,
+            ("mode", "god") => self.god = flag(&name, value)?,
+            ("mode", "endless") => self.endless = flag(&name, value)?,
+            ("mode", "adaptive") => self.adaptive = flag(&name, value)?,
+            ("mode", "bounce") => self.bounce = flag(&name, value)?,
+            ("mode", "fresh-food") => self.fresh_food = flag(&name, value)?,
+            ("mode", "limited-vision") => self.limited_vision = flag(&name, value)?,
+            ("mode", "demo") => self.demo = flag(&name, value)?,
+            ("mode", "players") => self.players = match value {
+                Value::Number(players @ 1..=2) => players as u16,
+                _ => return Err(format!("{} expects 1 or 2 players", name))
+            },
+            ("controls", "scheme") => self.scheme = named(&name, value, "one of: absolute, relative, left-hand, right-hand, vim", ControlScheme::from_name)?,
+            ("controls", "layout") => self.layout = named(&name, value, "one of: qwerty, azerty, dvorak, colemak", Layout::from_name)?,
+            ("controls", "mirror") => self.controls = named(&name, value, "one of: none, left-right, all", |mirror| match mirror {
+                "none" => Some(DirectionMap::identity()),
+                "left-right" => Some(DirectionMap::mirrored(false)),
+                "all" => Some(DirectionMap::mirrored(true)),
+                _ => None
+            })?,
+            ("controls", "shuffle") => self.shuffle_controls = flag(&name, value)?,
+            ("controls", "afk-timeout") => self.afk_timeout = match value {
+                Value::Number(seconds) => seconds,
+                _ => return Err(format!("{} expects a number of seconds", name))
+            },
+            ("display", "theme") => self.theme = named(&name, value, "one of: classic, neon, monochrome", Theme::from_name)?,
+            ("display", "ascii") => self.ascii = flag(&name, value)?,
+            ("display", "menu") => self.menu = flag(&name, value)?,
+            ("display", "ambient") => self.ambience = Some(named(&name, value, "one of: rain, stars", Ambience::from_name)?),
+            ("display", "milestone-borders") => self.milestone_borders = flag(&name, value)?,
+            ("display", "coordinates") => self.coordinates = flag(&name, value)?,
+            ("display", "head-position") => self.head_position = flag(&name, value)?,
+            ("display", "hints") => self.hints = flag(&name, value)?,
+            ("output", "screenshot-on-death") => self.screenshot_on_death = flag(&name, value)?,
+            ("output", "export-svg") => self.export_svg = flag(&name, value)?,
+            ("colors", "snake") => self.colors.snake = Some(named(&name, value, "a color", parse_color)?),
+            ("colors", "food") => self.colors.food = Some(named(&name, value, "a color", parse_color)?),
+            ("colors", "border") => self.colors.border = Some(named(&name, value, "a color", parse_color)?),
+            ("keys", action) => {
+                let keys = match value {
+                    Value::Text(key) => vec![key],
+                    Value::List(keys) => keys,
+                    _ => return Err(format!("{} expects a key or a list of keys", name))
+                };
+                let binding = format!("{}={}", action, keys.join(","));
+                let binding = keymap::parse_binding(&binding).ok_or_else(|| format!("{} isn't a valid binding", name))?;
+                self.bindings.push(binding);
+            }
+            _ => return Err(format!("unknown setting {}", name))
+        }
+
+        Ok(())
+    }
+
+    pub fn keymap(&self) -> Keymap {
+        let mut keymap = Keymap::new(self.scheme, self.layout);
+        for (action, keys) in &self.bindings {
+            keymap.bind(*action, keys);
+        }
+
+        keymap
+    }
+}
+
+/// Returns the path of the config file in the user's config directory, or `None` if there is no home directory.
+pub fn default_path() -> Option<PathBuf> {
+    let config_dir = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library").join("Application Support"))
+    } else {
+        // relative paths in XDG_CONFIG_HOME are invalid according to the XDG spec, and should be ignored
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+
+    config_dir.map(|dir| dir.join(CONFIG_DIR).join(FILE_NAME))
+}
+
+/// Looks up a color by its name, such as `green` or `dark-blue`.
+///
+/// # Parameters
+/// - `name`: The name of the color.
+///
+/// # Returns
+/// `Some(Color)` for a known name, otherwise `None`.
+pub fn parse_color(name: &str) -> Option<Color> {
+    match name {
+        "black" => Some(Color::Black),
+        "dark-grey" => Some(Color::DarkGrey),
+        "red" => Some(Color::Red),
+        "dark-red" => Some(Color::DarkRed),
+        "green" => Some(Color::Green),
+        "dark-green" => Some(Color::DarkGreen),
+        "yellow" => Some(Color::Yellow),
+        "dark-yellow" => Some(Color::DarkYellow),
+        "blue" => Some(Color::Blue),
+        "dark-blue" => Some(Color::DarkBlue),
+        "magenta" => Some(Color::Magenta),
+        "dark-magenta" => Some(Color::DarkMagenta),
+        "cyan" => Some(Color::Cyan),
+        "dark-cyan" => Some(Color::DarkCyan),
+        "white" => Some(Color::White),
+        "grey" => Some(Color::Grey),
+        _ => None
+    }
+}
+
+// parses the value of a setting, which has to be the only thing on the line apart from a comment
+fn parse_value(raw: &str) -> Option<Value> {
+    let raw = raw.trim();
+    let mut chars = raw.chars().peekable();
+
+    let value = match chars.next() {
+        Some('"') => Value::Text(parse_string(&mut chars)?),
+        Some('[') => Value::List(parse_list(&mut chars)?),
+        // without quotes, everything up to a comment is the value
+        _ => return match raw.split('#').next().unwrap_or_default().trim() {
+            "true" => Some(Value::Flag(true)),
+            "false" => Some(Value::Flag(false)),
+            number => number.parse().ok().map(Value::Number)
+        }
+    };
+
+    match skip_whitespace(&mut chars) {
+        None | Some('#') => Some(value),
+        Some(_) => None
+    }
+}
+
+// parses the rest of a quoted string after its opening quote, with \", \\, \n and \t escaped
+fn parse_string(chars: &mut Peekable<Chars>) -> Option<String> {
+    let mut text = String::new();
+
+    loop {
+        match chars.next()? {
+            '"' => return Some(text),
+            '\\' => text.push(match chars.next()? {
+                '"' => '"',
+                '\\' => '\\',
+                'n' => '\n',
+                't' => '\t',
+                _ => return None
+            }),
+            c => text.push(c)
+        }
+    }
+}
+
+// parses the rest of a list of quoted strings after its opening bracket, which may end in a comma
+fn parse_list(chars: &mut Peekable<Chars>) -> Option<Vec<String>> {
+    let mut items = Vec::new();
+
+    loop {
+        match skip_whitespace(chars)? {
+            ']' => {
+                chars.next();
+                return Some(items);
+            }
+            '"' => {
+                chars.next();
+                items.push(parse_string(chars)?);
+            }
+            _ => return None
+        }
+
+        match skip_whitespace(chars)? {
+            ',' => {
+                chars.next();
+            }
+            ']' => {}
+            _ => return None
+        }
+    }
+}
+
+// skips whitespace and returns the character after it without consuming it
+fn skip_whitespace(chars: &mut Peekable<Chars>) -> Option<char> {
+    while chars.peek().is_some_and(|c| c.is_whitespace()) {
+        chars.next();
+    }
+
+    chars.peek().copied()
+}
+
+// reads a true or false setting
+fn flag(name: &str, value: Value) -> Result<bool, String> {
+    match value {
+        Value::Flag(enabled) => Ok(enabled),
+        _ => Err(format!("{} expects true or false", name))
+    }
+}
+
+// reads a setting holding one of a few names
+fn named<T>(name: &str, value: Value, expected: &str, parse: impl Fn(&str) -> Option<T>) -> Result<T, String> {
+    match value {
+        Value::Text(text) => parse(&text),
+        _ => None
+    }.ok_or_else(|| format!("{} expects {}", name, expected))
+}
+
+// reads the width or height of the board, which has to leave room for the snake to start in either direction
+fn board_size(name: &str, value: Value) -> Result<u16, String> {
+    match value {
+        Value::Number(size) if (MIN_BOARD_SIZE as u64..=MAX_BOARD_SIZE as u64).contains(&size) => Ok(size as u16),
+        _ => Err(format!("{} expects a number of cells from {} to {}", name, MIN_BOARD_SIZE, MAX_BOARD_SIZE))
+    }
+}
+
+// reads a speed from 1 to MAX_SPEED
+fn speed(name: &str, value: Value) -> Result<u16, String> {
+    match value {
+        Value::Number(speed) if (1..=MAX_SPEED as u64).contains(&speed) => Ok(speed as u16),
+        _ => Err(format!("{} expects a speed from 1 to {}", name, MAX_SPEED))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str) -> Value {
+        Value::Text(String::from(text))
+    }
+
+    fn list(items: &[&str]) -> Value {
+        Value::List(items.iter().copied().map(String::from).collect())
+    }
+
+    #[test]
+    fn parses_every_kind_of_value() {
+        assert_eq!(parse_value(" 42 # the answer"), Some(Value::Number(42)));
+        assert_eq!(parse_value("true"), Some(Value::Flag(true)));
+        assert_eq!(parse_value(" \"maze\"  # a comment"), Some(text("maze")));
+        assert_eq!(parse_value("[\"x\", \"esc\",]"), Some(list(&["x", "esc"])));
+        assert_eq!(parse_value("[ ]"), Some(list(&[])));
+    }
+
+    #[test]
+    fn keeps_brackets_and_escaped_quotes_inside_strings() {
+        assert_eq!(parse_value("[\"]\"]"), Some(list(&["]"])));
+        assert_eq!(parse_value("[\"[\", \",\", \"#\"] # brackets"), Some(list(&["[", ",", "#"])));
+        assert_eq!(parse_value("\"say \\\"hi\\\" \\\\ bye\""), Some(text("say \"hi\" \\ bye")));
+        assert_eq!(parse_value("[\"\\\"\"]"), Some(list(&["\""])));
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        for raw in ["", "yes", "-1", "\"open", "\"open\" closed", "[\"x\"", "[x]", "[\"x\" \"y\"]", "\"\\q\"", "[\"x\"] y"] {
+            assert_eq!(parse_value(raw), None, "{}", raw);
+        }
+    }
+
+    #[test]
+    fn loads_a_file_over_the_defaults() {
+        let path = env::temp_dir().join(format!("snake-config-test-{}.toml", std::process::id()));
+        fs::write(&path, "# settings\n[board]\nwidth = 40 # wide\n\n[mode]\nhardcore = true\n\n[keys]\npause = [\"]\", \"p\"]\n").unwrap();
+
+        let mut config = Config::default();
+        let result = config.load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(result.is_ok(), "{:?}", result);
+        assert_eq!((config.width, config.height, config.hardcore), (40, DEFAULT_HEIGHT, true));
+        assert_eq!(config.bindings, [(Action::Pause, vec![KeyCode::Char(']'), KeyCode::Char('p')])]);
+    }
+
+    #[test]
+    fn reports_the_line_of_an_invalid_setting() {
+        let path = env::temp_dir().join(format!("snake-config-error-test-{}.toml", std::process::id()));
+        fs::write(&path, "[board]\nwidth = 40\nheight = 2\n").unwrap();
+
+        let error = Config::default().load(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(error.to_string(), "line 3: board.height expects a number of cells from 5 to 200");
+        assert_eq!(Config::default().set("board", "depth", Value::Number(3)), Err(String::from("unknown setting board.depth")));
+    }
+}
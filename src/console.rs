@@ -0,0 +1,63 @@
+use crate::points::Point;
+
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents a command entered in the developer console.
+///
+/// # Variants
+/// - `SpawnFood`: Places the food on a cell (`spawn food <x> <y>`).
+/// - `SetSpeed`: Changes the speed of the game (`set speed <speed>`).
+/// - `Teleport`: Moves the snake so its head is on a cell (`teleport <x> <y>`).
+/// - `Grow`: Lets the snake grow a number of segments (`grow <segments>`).
+/// - `Seed`: Replaces the source of random decisions with one seeded with a value (`seed <seed>`).
+///
+/// # Methods
+/// ## `parse`
+/// Interprets a line typed into the console.
+///
+/// ### Parameters
+/// - `line`: The line to interpret.
+///
+/// ### Returns
+/// The `ConsoleCommand` on the line, or a message explaining why it isn't one.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::console::ConsoleCommand;
+/// # use snake_the_sequel::points::Point;
+/// let command = ConsoleCommand::parse("spawn food 5 5");
+/// assert_eq!(command, Ok(ConsoleCommand::SpawnFood(Point::new(5, 5))));
+/// ```
+pub enum ConsoleCommand {
+    SpawnFood(Point),
+    SetSpeed(u16),
+    Teleport(Point),
+    Grow(u16),
+    Seed(u64)
+}
+
+impl ConsoleCommand {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        match words.as_slice() {
+            ["spawn", "food", x, y] => Ok(Self::SpawnFood(Point::new(parse_number(x)?, parse_number(y)?))),
+            ["set", "speed", speed] => Ok(Self::SetSpeed(parse_number(speed)?)),
+            ["teleport", x, y] => Ok(Self::Teleport(Point::new(parse_number(x)?, parse_number(y)?))),
+            ["grow", segments] => Ok(Self::Grow(parse_number(segments)?)),
+            ["seed", seed] => Ok(Self::Seed(parse_number(seed)?)),
+            ["spawn", ..] => Err(String::from("Usage: spawn food <x> <y>")),
+            ["set", ..] => Err(String::from("Usage: set speed <speed>")),
+            ["teleport", ..] => Err(String::from("Usage: teleport <x> <y>")),
+            ["grow", ..] => Err(String::from("Usage: grow <segments>")),
+            ["seed", ..] => Err(String::from("Usage: seed <seed>")),
+            [] => Err(String::from("No command")),
+            [command, ..] => Err(format!("Unknown command {}", command))
+        }
+    }
+}
+
+fn parse_number<T: FromStr>(word: &str) -> Result<T, String> {
+    word.parse().map_err(|_| format!("Invalid number {}", word))
+}
@@ -0,0 +1,355 @@
+use crate::command::Command;
+use crate::direction::Direction;
+use crate::random::RandomSource;
+use crate::theme::Glyphs;
+
+use std::fmt;
+use std::time::Duration;
+use crossterm::event::KeyCode;
+
+const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+const SHUFFLE_PERIOD: Duration = Duration::from_secs(20); // play time between two shuffles
+const SHUFFLE_WARNING: Duration = Duration::from_secs(3); // play time before a shuffle that the upcoming mapping is shown
+// the three letter rows of each layout, so a key can be found at the same position on a QWERTY keyboard
+const QWERTY_ROWS: &str = "qwertyuiopasdfghjkl;zxcvbnm,./";
+const AZERTY_ROWS: &str = "azertyuiopqsdfghjklmwxcvbn,;:!";
+const DVORAK_ROWS: &str = "',.pyfgcrlaoeuidhtns;qjkxbmwvz";
+const COLEMAK_ROWS: &str = "qwfpgjluy;arstdhneiozxcvbkm,./";
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents the keys used to steer the snake.
+///
+/// The arrow keys always turn the snake in the direction they point to. A `ControlScheme` can add
+/// other keys on top of them, which the `Keymap` binds by their position on a QWERTY keyboard.
+///
+/// # Variants
+/// - `Absolute`: Only the arrow keys, pointing in the direction to turn to.
+/// - `Relative`: Two keys on a single row, `j`/`,` and `k`/`.`, turning the snake left or right of its
+///   current heading, which suits phone keyboards without arrow keys.
+/// - `LeftHand`: `WASD` for playing with the left hand only.
+/// - `RightHand`: `IJKL` for playing with the right hand only.
+/// - `Vim`: `HJKL` as in vim, with `h` turning left, `j` down, `k` up and `l` right.
+///
+/// # Methods
+/// ## `from_name`
+/// Looks up a control scheme by its name (`absolute`, `relative`, `left-hand`, `right-hand` or `vim`).
+///
+/// ### Returns
+/// `Some(ControlScheme)` for a known name, otherwise `None`.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::controls::{ControlScheme, Layout};
+/// # use snake_the_sequel::keymap::Keymap;
+/// let scheme = ControlScheme::from_name("vim").unwrap();
+/// let keymap = Keymap::new(scheme, Layout::Qwerty);
+/// ```
+pub enum ControlScheme {
+    Absolute,
+    Relative,
+    LeftHand,
+    RightHand,
+    Vim
+}
+
+impl ControlScheme {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "absolute" => Some(Self::Absolute),
+            "relative" => Some(Self::Relative),
+            "left-hand" => Some(Self::LeftHand),
+            "right-hand" => Some(Self::RightHand),
+            "vim" => Some(Self::Vim),
+            _ => None
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents the keyboard layout the player types on.
+///
+/// The letter keys of the control schemes are defined by their position on a QWERTY keyboard, so
+/// `WASD` stays under the left hand on other layouts too: on AZERTY it's `ZQSD`, on Dvorak `,AOE`
+/// and on Colemak `WARS`.
+///
+/// # Variants
+/// - `Qwerty`: The QWERTY layout, which the control schemes are defined on.
+/// - `Azerty`: The French AZERTY layout.
+/// - `Dvorak`: The Dvorak simplified keyboard.
+/// - `Colemak`: The Colemak layout.
+///
+/// # Methods
+/// ## `from_name`
+/// Looks up a layout by its name (`qwerty`, `azerty`, `dvorak` or `colemak`).
+///
+/// ### Returns
+/// `Some(Layout)` for a known name, otherwise `None`.
+///
+/// ## `to_qwerty`
+/// Translates a key into the key at the same position on a QWERTY keyboard.
+///
+/// ### Parameters
+/// - `code`: The code of the pressed key.
+///
+/// ### Returns
+/// The `KeyCode` of the QWERTY key, or `code` itself if it isn't one of the letter rows.
+///
+/// # Example
+/// ```rust
+/// # use crossterm::event::KeyCode;
+/// # use snake_the_sequel::controls::Layout;
+/// assert_eq!(Layout::Azerty.to_qwerty(KeyCode::Char('z')), KeyCode::Char('w'));
+/// ```
+pub enum Layout {
+    Qwerty,
+    Azerty,
+    Dvorak,
+    Colemak
+}
+
+impl Layout {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "qwerty" => Some(Self::Qwerty),
+            "azerty" => Some(Self::Azerty),
+            "dvorak" => Some(Self::Dvorak),
+            "colemak" => Some(Self::Colemak),
+            _ => None
+        }
+    }
+
+    pub fn to_qwerty(self, code: KeyCode) -> KeyCode {
+        let rows = match self {
+            Self::Qwerty => return code,
+            Self::Azerty => AZERTY_ROWS,
+            Self::Dvorak => DVORAK_ROWS,
+            Self::Colemak => COLEMAK_ROWS
+        };
+
+        match code {
+            KeyCode::Char(c) => rows.chars()
+                .position(|key| key == c.to_ascii_lowercase())
+                .and_then(|i| QWERTY_ROWS.chars().nth(i))
+                .map_or(code, KeyCode::Char),
+            _ => code
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents a remapping of the direction controls.
+///
+/// The `DirectionMap` sits between the input mapping and the snake: every `Command::Turn` read from
+/// the keyboard is passed through it, so challenge modifiers can change what the direction keys do
+/// without touching the key handling itself.
+///
+/// # Fields
+/// - `targets`: The direction each of `Up`, `Right`, `Down` and `Left` is turned into, in that order.
+///
+/// # Methods
+/// ## `identity`
+/// Creates a `DirectionMap` that leaves every direction as it is.
+///
+/// ## `mirrored`
+/// Creates a `DirectionMap` that swaps left and right, and optionally up and down as well.
+///
+/// ### Parameters
+/// - `all`: Whether up and down should be swapped too.
+///
+/// ## `shuffled`
+/// Creates a `DirectionMap` that assigns the directions to the keys in a random order, never leaving
+/// all of them in place.
+///
+/// ### Parameters
+/// - `random`: The `RandomSource` used to shuffle the directions.
+///
+/// ## `map`
+/// Returns the direction a given direction is remapped to.
+///
+/// ## `apply`
+/// Remaps the direction of a `Command::Turn`, passing every other command through unchanged.
+///
+/// ### Parameters
+/// - `command`: The `Command` read from the input.
+///
+/// ### Returns
+/// The `Command` that should be executed.
+///
+/// ## `describe`
+/// Describes the mapping as `key=direction` pairs, e.g. `↑=→ →=↓ ↓=← ←=↑`, which is also how it's
+/// displayed.
+///
+/// ### Parameters
+/// - `arrows`: The arrows pointing up, right, down and left to draw the directions with.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::controls::DirectionMap;
+/// # use snake_the_sequel::direction::Direction;
+/// let mirror = DirectionMap::mirrored(false);
+/// assert_eq!(mirror.map(Direction::Left), Direction::Right);
+/// assert_eq!(mirror.map(Direction::Up), Direction::Up);
+/// ```
+pub struct DirectionMap {
+    targets: [Direction; 4]
+}
+
+impl DirectionMap {
+    pub fn identity() -> Self {
+        Self { targets: DIRECTIONS }
+    }
+
+    pub fn mirrored(all: bool) -> Self {
+        if all {
+            Self { targets: [Direction::Down, Direction::Left, Direction::Up, Direction::Right] }
+        } else {
+            Self { targets: [Direction::Up, Direction::Left, Direction::Down, Direction::Right] }
+        }
+    }
+
+    pub fn shuffled(random: &mut dyn RandomSource) -> Self {
+        let mut targets = DIRECTIONS;
+        for i in (1..targets.len()).rev() {
+            targets.swap(i, random.gen_range(0, i as u16 + 1) as usize);
+        }
+
+        // a shuffle that happens to change nothing would go unnoticed, so it's turned a quarter instead
+        if targets == DIRECTIONS {
+            targets.rotate_left(1);
+        }
+
+        Self { targets }
+    }
+
+    pub fn map(&self, direction: Direction) -> Direction {
+        let index = DIRECTIONS.iter().position(|&d| d == direction).unwrap();
+        self.targets[index]
+    }
+
+    pub fn apply(&self, command: Command) -> Command {
+        match command {
+            Command::Turn(direction) => Command::Turn(self.map(direction)),
+            other => other
+        }
+    }
+
+    pub fn describe(&self, arrows: [char; 4]) -> String {
+        let arrow = |direction: Direction| arrows[DIRECTIONS.iter().position(|&d| d == direction).unwrap()];
+
+        let pairs: Vec<String> = DIRECTIONS.iter()
+            .map(|&key| format!("{}={}", arrow(key), arrow(self.map(key))))
+            .collect();
+        pairs.join(" ")
+    }
+}
+
+impl fmt::Display for DirectionMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.describe(Glyphs::UNICODE.arrows))
+    }
+}
+
+#[derive(Debug)]
+/// Represents the randomized-controls chaos modifier.
+///
+/// The `ControlShuffler` hands out a new random `DirectionMap` every `SHUFFLE_PERIOD`, and makes the
+/// upcoming mapping known `SHUFFLE_WARNING` in advance so it can be shown to the player. The time is
+/// the play time the game adds up tick by tick rather than the clock, so a seeded game shuffles at the
+/// same moments every time it's played.
+///
+/// # Fields
+/// - `last_shuffle`: The play time the controls were last shuffled at (or the shuffler was created at).
+/// - `upcoming`: The mapping that will be handed out at the next shuffle.
+///
+/// # Methods
+/// ## `new`
+/// Creates a new `ControlShuffler`, with the first shuffle one period from now.
+///
+/// ### Parameters
+/// - `play_time`: The time the game has been played.
+/// - `random`: The `RandomSource` used to pick the first mapping.
+///
+/// ## `get_warning`
+/// Returns the upcoming mapping and the time left until it's applied, once the shuffle is close.
+///
+/// ### Parameters
+/// - `play_time`: The time the game has been played.
+///
+/// ### Returns
+/// `Some((DirectionMap, Duration))` during the warning period, otherwise `None`.
+///
+/// ## `poll`
+/// Checks if it's time to shuffle the controls.
+///
+/// ### Parameters
+/// - `play_time`: The time the game has been played.
+/// - `random`: The `RandomSource` used to pick the mapping after this one.
+///
+/// ### Returns
+/// `Some(DirectionMap)` with the new mapping when the controls should change, otherwise `None`.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::controls::ControlShuffler;
+/// # use std::time::Duration;
+/// # use snake_the_sequel::random::ThreadRandom;
+/// let mut shuffler = ControlShuffler::new(Duration::ZERO, &mut ThreadRandom);
+/// if let Some(controls) = shuffler.poll(Duration::from_secs(20), &mut ThreadRandom) {
+///     // apply the new controls
+/// }
+/// ```
+pub struct ControlShuffler {
+    last_shuffle: Duration,
+    upcoming: DirectionMap
+}
+
+impl ControlShuffler {
+    pub fn new(play_time: Duration, random: &mut dyn RandomSource) -> Self {
+        Self { last_shuffle: play_time, upcoming: DirectionMap::shuffled(random) }
+    }
+
+    pub fn get_warning(&self, play_time: Duration) -> Option<(DirectionMap, Duration)> {
+        let left = (self.last_shuffle + SHUFFLE_PERIOD).checked_sub(play_time)?;
+
+        if left <= SHUFFLE_WARNING {
+            Some((self.upcoming, left))
+        } else {
+            None
+        }
+    }
+
+    pub fn poll(&mut self, play_time: Duration, random: &mut dyn RandomSource) -> Option<DirectionMap> {
+        if play_time.saturating_sub(self.last_shuffle) < SHUFFLE_PERIOD {
+            return None;
+        }
+
+        self.last_shuffle = play_time;
+        Some(std::mem::replace(&mut self.upcoming, DirectionMap::shuffled(random)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::SeededRandom;
+
+    #[test]
+    fn describes_the_mapping_with_the_arrows_given() {
+        let mirror = DirectionMap::mirrored(false);
+
+        assert_eq!(mirror.describe(Glyphs::ASCII.arrows), "^=^ >=< v=v <=>");
+        assert_eq!(mirror.to_string(), "↑=↑ →=← ↓=↓ ←=→");
+    }
+
+    #[test]
+    fn shuffles_every_period_of_play_time() {
+        let mut random = SeededRandom::new(0);
+        let mut shuffler = ControlShuffler::new(Duration::from_secs(5), &mut random);
+
+        assert!(shuffler.get_warning(Duration::from_secs(21)).is_none());
+        assert_eq!(shuffler.get_warning(Duration::from_secs(23)).map(|(_, left)| left), Some(Duration::from_secs(2)));
+        assert!(shuffler.poll(Duration::from_secs(24), &mut random).is_none());
+        assert!(shuffler.poll(Duration::from_secs(25), &mut random).is_some());
+        assert!(shuffler.poll(Duration::from_secs(26), &mut random).is_none());
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::{self, stdout, Write};
+use std::panic;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crossterm::ExecutableCommand;
+use crossterm::cursor::Show;
+use crossterm::style::ResetColor;
+use crossterm::terminal::disable_raw_mode;
+
+use crate::config::Config;
+
+const MAX_EVENTS: usize = 50; // number of recent log messages kept for the crash report
+
+static ARGUMENTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static PANIC: Mutex<String> = Mutex::new(String::new());
+static EVENTS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+static CONFIG: Mutex<String> = Mutex::new(String::new());
+static SNAPSHOT: Mutex<String> = Mutex::new(String::new());
+
+/// Installs a panic hook that restores the terminal and remembers the panic for the crash report before
+/// the panic message is printed.
+///
+/// The report itself is written with `write_report` once the panic has unwound, so the game can add its
+/// state on the way out.
+pub fn install(args: Vec<String>) {
+    *lock(&ARGUMENTS) = args;
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        // the game may have panicked in raw mode, which would leave the shell unusable
+        let _ = disable_raw_mode();
+        let _ = stdout().execute(Show).and_then(|stdout| stdout.execute(ResetColor));
+
+        *lock(&PANIC) = info.to_string();
+        default_hook(info);
+    }));
+}
+
+/// Remembers a log message for the crash report, forgetting the oldest one once `MAX_EVENTS` are kept.
+pub fn record_event(event: String) {
+    let mut events = lock(&EVENTS);
+    if events.len() == MAX_EVENTS {
+        events.pop_front();
+    }
+    events.push_back(event);
+}
+
+/// Remembers the settings the game is played with for the crash report.
+pub fn record_config(config: &Config) {
+    *lock(&CONFIG) = format!("{:#?}", config);
+}
+
+/// Replaces the game state included in the crash report, which the game records when it fails.
+pub fn record_snapshot(snapshot: String) {
+    *lock(&SNAPSHOT) = snapshot;
+}
+
+/// Writes a crash report with the arguments, the settings, the recent events and the last game state
+/// to `snake-crash-<time>.txt` in the working directory, where the time is in milliseconds.
+///
+/// # Parameters
+/// - `error`: The error the game stopped with, or `None` if it panicked, in which case the panic
+///   remembered by the hook is reported.
+///
+/// # Returns
+/// The name of the report, or an error if it couldn't be written.
+///
+/// # Example
+/// ```no_run
+/// # use std::io::stdout;
+/// # use std::panic::{self, AssertUnwindSafe};
+/// # use snake_the_sequel::config::Config;
+/// # use snake_the_sequel::crash;
+/// # use snake_the_sequel::game::Game;
+/// crash::install(std::env::args().collect());
+/// let mut game = Game::new(stdout(), &Config::default());
+/// let error = match panic::catch_unwind(AssertUnwindSafe(|| game.run())) {
+///     Ok(Ok(())) => return,
+///     Ok(Err(error)) => Some(error.to_string()),
+///     Err(_) => None
+/// };
+/// let name = crash::write_report(error.as_deref()).unwrap();
+/// eprintln!("Crash report written to {}", name);
+/// ```
+pub fn write_report(error: Option<&str>) -> io::Result<String> {
+    let failure = match error {
+        Some(error) => String::from(error),
+        None => lock(&PANIC).clone()
+    };
+
+    let mut report = String::new();
+    report.push_str(&format!("{} {}\n", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")));
+    report.push_str(&format!("arguments: {}\n", lock(&ARGUMENTS).join(" ")));
+    report.push_str(&format!("\n{}\n", failure));
+    report.push_str("\nrecent events:\n");
+    for event in lock(&EVENTS).iter() {
+        report.push_str(&format!("  {}\n", event));
+    }
+    report.push_str(&format!("\nconfig:\n{}\n", lock(&CONFIG)));
+    report.push_str(&format!("\nlast game state:\n{}\n", lock(&SNAPSHOT)));
+
+    // a report written in the same millisecond, e.g. by another instance, is never overwritten
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+    let mut name = format!("snake-crash-{}.txt", timestamp);
+    let mut count = 1;
+    let mut file = loop {
+        match OpenOptions::new().write(true).create_new(true).open(&name) {
+            Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {
+                count += 1;
+                name = format!("snake-crash-{}-{}.txt", timestamp, count);
+            }
+            result => break result?
+        }
+    };
+    file.write_all(report.as_bytes())?;
+
+    Ok(name)
+}
+
+// locks one of the parts of the report, which stays usable even if a panic poisoned the lock
+fn lock<T>(part: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    part.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
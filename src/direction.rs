@@ -16,9 +16,15 @@
 /// Returns the opposite direction of the current direction.
 /// For example, `Up`'s opposite is `Down`, and `Right`'s opposite is `Left`.
 ///
+/// ## `clockwise`
+/// Returns the direction a quarter turn to the right of the current direction.
+///
+/// ## `counter_clockwise`
+/// Returns the direction a quarter turn to the left of the current direction.
+///
 /// # Example
 /// ```rust
-/// use crate::direction::Direction;
+/// use snake_the_sequel::direction::Direction;
 ///
 /// let up = Direction::Up;
 /// let down = up.opposite(); // Direction::Down
@@ -39,4 +45,17 @@ impl Direction {
             Self::Left => Self::Right
         }
     }
+
+    pub fn clockwise(&self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up
+        }
+    }
+
+    pub fn counter_clockwise(&self) -> Self {
+        self.clockwise().opposite()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,392 @@
+use crate::points::Point;
+
+use crossterm::style::Color;
+use rand::Rng;
+
+const RAIN_DENSITY: u32 = 4; // chance of a new drop per column per frame, in percent
+const STAR_DENSITY: u32 = 6; // percentage of cells holding a star
+const STAR_PERIOD: u8 = 12; // frames it takes a star to twinkle once
+const BURST_GLYPHS: [char; 3] = ['+', '*', '·']; // glyph of the ring in each frame of a burst
+const TRAIL_GLYPHS: [(char, Color); 3] = [('·', Color::Grey), ('·', Color::DarkGrey), ('.', Color::DarkGrey)];
+const TOAST_FRAMES: u8 = 10;
+// the board can only move right and down, as it is drawn against the top left corner of the screen
+const SHAKE_OFFSETS: [(u16, u16); 6] = [(1, 0), (0, 1), (1, 1), (0, 0), (1, 0), (0, 0)];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents the kind of ambient effect rendered behind the game.
+///
+/// # Variants
+/// - `Rain`: Dots falling down the board.
+/// - `Stars`: Stars that twinkle in place.
+///
+/// # Methods
+/// ## `from_name`
+/// Looks up an ambience by its name (`rain` or `stars`).
+///
+/// ### Returns
+/// `Some(Ambience)` for a known name, otherwise `None`.
+pub enum Ambience {
+    Rain,
+    Stars
+}
+
+impl Ambience {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "rain" => Some(Self::Rain),
+            "stars" => Some(Self::Stars),
+            _ => None
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Represents the ambient background layer of the board.
+///
+/// The `AmbientLayer` animates subtle decorations in the background of the board. It only decides
+/// which glyph a cell would show, the renderer is responsible for skipping cells that are occupied
+/// by gameplay elements.
+///
+/// # Fields
+/// - `ambience`: The kind of effect being rendered.
+/// - `width`: The width of the game grid.
+/// - `height`: The height of the game grid.
+/// - `cells`: The decorated cells, each with its current animation phase.
+///
+/// # Methods
+/// ## `new`
+/// Creates a new `AmbientLayer` for a grid of the given size.
+///
+/// ### Parameters
+/// - `ambience`: The kind of effect to render.
+/// - `width`: The width of the game grid.
+/// - `height`: The height of the game grid.
+///
+/// ## `update`
+/// Advances the animation by one frame.
+///
+/// ## `glyph_at`
+/// Returns the glyph to show on a cell.
+///
+/// ### Parameters
+/// - `point`: A reference to the `Point` of the cell.
+///
+/// ### Returns
+/// `Some(char)` if the cell is decorated, otherwise `None`.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::effects::{Ambience, AmbientLayer};
+/// # use snake_the_sequel::points::Point;
+/// let mut layer = AmbientLayer::new(Ambience::Rain, 30, 10);
+/// layer.update();
+/// let glyph = layer.glyph_at(&Point::new(3, 0));
+/// ```
+pub struct AmbientLayer {
+    ambience: Ambience,
+    width: u16,
+    height: u16,
+    cells: Vec<(Point, u8)>
+}
+
+impl AmbientLayer {
+    pub fn new(ambience: Ambience, width: u16, height: u16) -> Self {
+        let mut rng = rand::thread_rng();
+        let mut cells = Vec::new();
+
+        if ambience == Ambience::Stars {
+            for y in 0..height {
+                for x in 0..width {
+                    if rng.gen_range(0, 100) < STAR_DENSITY {
+                        cells.push((Point::new(x, y), rng.gen_range(0, STAR_PERIOD)));
+                    }
+                }
+            }
+        }
+
+        Self { ambience, width, height, cells }
+    }
+
+    pub fn update(&mut self) {
+        let mut rng = rand::thread_rng();
+
+        match self.ambience {
+            Ambience::Rain => {
+                let height = self.height;
+                for (drop, _) in self.cells.iter_mut() {
+                    drop.y += 1;
+                }
+                self.cells.retain(|(drop, _)| drop.y < height);
+
+                for x in 0..self.width {
+                    if rng.gen_range(0, 100) < RAIN_DENSITY {
+                        self.cells.push((Point::new(x, 0), 0));
+                    }
+                }
+            }
+            Ambience::Stars => {
+                for (_, phase) in self.cells.iter_mut() {
+                    *phase = (*phase + 1) % STAR_PERIOD;
+                }
+            }
+        }
+    }
+
+    pub fn glyph_at(&self, point: &Point) -> Option<char> {
+        let &(_, phase) = self.cells.iter().find(|(cell, _)| cell == point)?;
+
+        match self.ambience {
+            Ambience::Rain => Some('.'),
+            Ambience::Stars => if phase == 0 {
+                Some('*')
+            } else if phase == 1 || phase == STAR_PERIOD - 1 {
+                Some('+')
+            } else {
+                Some('·')
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Represents the particle bursts shown when the snake eats.
+///
+/// Every burst is a ring that expands by one cell per frame around the point it was spawned at,
+/// changing its glyph as it fades, until it disappears after `BURST_GLYPHS.len()` frames.
+///
+/// # Fields
+/// - `width`: The width of the game grid.
+/// - `height`: The height of the game grid.
+/// - `bursts`: The active bursts, each with its origin and age in frames.
+///
+/// # Methods
+/// ## `new`
+/// Creates a new `Particles` layer without any active bursts.
+///
+/// ### Parameters
+/// - `width`: The width of the game grid.
+/// - `height`: The height of the game grid.
+///
+/// ## `spawn`
+/// Starts a new burst.
+///
+/// ### Parameters
+/// - `origin`: The `Point` the ring expands from.
+///
+/// ## `update`
+/// Advances all bursts by one frame, removing the ones that have faded.
+///
+/// ## `cells`
+/// Returns the cells covered by particles in the current frame.
+///
+/// ### Returns
+/// A `Vec<(Point, char)>` with the position and glyph of each particle.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::effects::Particles;
+/// # use snake_the_sequel::points::Point;
+/// let mut particles = Particles::new(30, 10);
+/// particles.spawn(Point::new(5, 5));
+/// let ring = particles.cells(); // the 8 cells around (5, 5)
+/// ```
+pub struct Particles {
+    width: u16,
+    height: u16,
+    bursts: Vec<(Point, u8)>
+}
+
+impl Particles {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self { width, height, bursts: Vec::new() }
+    }
+
+    pub fn spawn(&mut self, origin: Point) {
+        self.bursts.push((origin, 0));
+    }
+
+    pub fn update(&mut self) {
+        for (_, age) in self.bursts.iter_mut() {
+            *age += 1;
+        }
+        self.bursts.retain(|&(_, age)| (age as usize) < BURST_GLYPHS.len());
+    }
+
+    pub fn cells(&self) -> Vec<(Point, char)> {
+        let mut cells = Vec::new();
+
+        for &(origin, age) in self.bursts.iter() {
+            let radius = age as i32 + 1;
+            let glyph = BURST_GLYPHS[age as usize];
+
+            for (dx, dy) in [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)].iter() {
+                let x = origin.x as i32 + dx * radius;
+                let y = origin.y as i32 + dy * radius;
+
+                if x >= 0 && y >= 0 && x < self.width as i32 && y < self.height as i32 {
+                    cells.push((Point::new(x as u16, y as u16), glyph));
+                }
+            }
+        }
+
+        cells
+    }
+}
+
+#[derive(Debug, Default)]
+/// Represents a screen shake.
+///
+/// The `ScreenShake` is an iterator over the offsets at which the board should be rendered in the
+/// consecutive frames of the shake, ending with the board back in its original position.
+///
+/// # Fields
+/// - `frame`: The number of frames played so far.
+///
+/// # Methods
+/// ## `new`
+/// Creates a new `ScreenShake` starting at its first frame.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::effects::ScreenShake;
+/// for (dx, dy) in ScreenShake::new() {
+///     // render the board moved by dx columns and dy rows
+/// }
+/// ```
+pub struct ScreenShake {
+    frame: usize
+}
+
+impl ScreenShake {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Iterator for ScreenShake {
+    type Item = (u16, u16);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = SHAKE_OFFSETS.get(self.frame).copied();
+        self.frame += 1;
+        offset
+    }
+}
+
+#[derive(Debug, Default)]
+/// Represents the fading trail behind the snake.
+///
+/// The `Trail` remembers the cells the snake's tail recently vacated and fades them out over
+/// `TRAIL_GLYPHS.len()` frames, which makes the motion of the snake easier to follow.
+///
+/// # Fields
+/// - `cells`: The vacated cells, each with its age in frames.
+///
+/// # Methods
+/// ## `new`
+/// Creates an empty `Trail`.
+///
+/// ## `leave`
+/// Adds a freshly vacated cell to the trail.
+///
+/// ### Parameters
+/// - `point`: The `Point` the tail just left.
+///
+/// ## `update`
+/// Ages the trail by one frame, removing the cells that have faded completely.
+///
+/// ## `cells`
+/// Returns the cells of the trail with the glyph and color to draw them in.
+///
+/// ### Returns
+/// A `Vec<(Point, char, Color)>` for all cells that are still visible.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::effects::Trail;
+/// # use snake_the_sequel::points::Point;
+/// let mut trail = Trail::new();
+/// trail.leave(Point::new(3, 4));
+/// trail.update();
+/// ```
+pub struct Trail {
+    cells: Vec<(Point, u8)>
+}
+
+impl Trail {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn leave(&mut self, point: Point) {
+        self.cells.push((point, 0));
+    }
+
+    pub fn update(&mut self) {
+        for (_, age) in self.cells.iter_mut() {
+            *age += 1;
+        }
+        self.cells.retain(|&(_, age)| (age as usize) < TRAIL_GLYPHS.len());
+    }
+
+    pub fn cells(&self) -> Vec<(Point, char, Color)> {
+        self.cells.iter()
+            .map(|&(point, age)| {
+                let (glyph, color) = TRAIL_GLYPHS[age as usize];
+                (point, glyph, color)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+/// Represents a short notification shown on top of the board.
+///
+/// # Fields
+/// - `text`: The text of the notification.
+/// - `frames_left`: The number of frames the notification remains visible.
+///
+/// # Methods
+/// ## `new`
+/// Creates a new `Toast` that stays visible for `TOAST_FRAMES` frames.
+///
+/// ### Parameters
+/// - `text`: The text to show.
+///
+/// ## `update`
+/// Counts down the remaining frames by one.
+///
+/// ## `is_visible`
+/// Checks if the notification should still be shown.
+///
+/// ## `get_text`
+/// Returns the text of the notification.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::effects::Toast;
+/// let toast = Toast::new(String::from("Length 20!"));
+/// assert!(toast.is_visible());
+/// ```
+pub struct Toast {
+    text: String,
+    frames_left: u8
+}
+
+impl Toast {
+    pub fn new(text: String) -> Self {
+        Self { text, frames_left: TOAST_FRAMES }
+    }
+
+    pub fn update(&mut self) {
+        self.frames_left = self.frames_left.saturating_sub(1);
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.frames_left > 0
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+}
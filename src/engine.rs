@@ -0,0 +1,1534 @@
+use crate::bug::Bug;
+use crate::command::Command;
+use crate::direction::Direction;
+use crate::food::{Food, FoodKind};
+use crate::points::Point;
+use crate::powerups::{ActiveEffect, PowerUp};
+use crate::random::{RandomSource, ThreadRandom};
+use crate::snake::Snake;
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+pub const MAX_SPEED: u16 = 20;
+pub const MIN_BOARD_SIZE: u16 = 5; // the snake starts three cells long in the middle of the board, heading anywhere
+pub const MAX_BOARD_SIZE: u16 = 200; // keeps the number of cells within a u16
+const START_LENGTH: u16 = 3;
+const PLAYER_OFFSET: u16 = 2; // columns between the middle of the board and each snake in two player games
+const DECAY_PER_SEGMENT: f32 = 0.01; // points lost per second for every segment of the snake in hardcore mode
+const BUG_MIN_DELAY: u16 = 30; // ticks between a bug leaving and the next one appearing
+const BUG_MAX_DELAY: u16 = 80;
+const FRESH_FOOD_POINTS: u16 = 5; // value of freshly placed food with fresh food scoring
+const FRESH_FOOD_DECAY: u64 = 10; // ticks it takes food to lose a point with fresh food scoring
+const BOUNCE_PENALTY: u16 = 1; // points lost every time the snake bounces off a wall
+const COIN_CHANCE: u16 = 2; // chance of a coin appearing on a tick without one, in percent
+const SPECIAL_FOOD_CHANCE: u16 = 1; // chance of bonus or shrink food appearing on a tick without any, in percent
+const SPECIAL_FOOD_LIFETIME: u64 = 30; // ticks bonus and shrink food stay on the board
+const POWER_UP_CHANCE: u16 = 1; // chance of a power-up appearing on a tick without one, in percent
+const POWER_UP_LIFETIME: u64 = 50; // ticks a power-up stays on the board before it disappears
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents the outcome of a step of the `GameEngine`.
+///
+/// # Variants
+/// - `Running`: The snake moved and the game goes on.
+/// - `Blocked`: The snake ran into something but collisions aren't fatal, so it waits for a turn.
+/// - `Over`: The game ended, because a snake crashed or the player quit.
+pub enum GameStatus {
+    Running,
+    Blocked,
+    Over
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents something that happened during a step of the `GameEngine`, for a frontend to react to.
+///
+/// # Variants
+/// - `LeftCell`: The snake's tail moved off a cell, which is free again.
+/// - `AteFood`: The snake ate food of the given kind on a cell, worth the given number of points.
+/// - `CaughtBug`: The snake caught the bug at a cell, worth the given number of points.
+/// - `CollectedCoin`: The snake picked up the coin on a cell.
+/// - `CollectedPowerUp`: The snake picked up a power-up on a cell, whose effect is now active.
+/// - `Collided`: The snake's head, at the given cell, ran into a wall or its own body.
+pub enum GameEvent {
+    LeftCell(Point),
+    AteFood(Point, FoodKind, u16),
+    CaughtBug(Point, u16),
+    CollectedCoin(Point),
+    CollectedPowerUp(Point, PowerUp),
+    Collided(Point)
+}
+
+#[derive(Debug)]
+/// Represents one of the players, each steering their own snake.
+///
+/// # Fields
+/// - `snake`: The player's snake.
+/// - `heading`: The direction the snake moved in on the last step, which turns are checked against so
+///   two quick turns within a step can't reverse the snake.
+/// - `score`: The player's score.
+/// - `decay`: The fraction of a point the score has decayed by, but not yet lost.
+/// - `crashed`: Whether the snake crashed, which ends the game.
+///
+/// # Methods
+/// ## `new`
+/// Creates a new `Player` with no points, heading the way the snake is facing.
+///
+/// ### Parameters
+/// - `snake`: The player's snake.
+///
+/// ## `get_snake`
+/// Returns the player's snake.
+///
+/// ## `get_score`
+/// Returns the player's score.
+///
+/// ## `has_crashed`
+/// Checks if the player's snake crashed.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::engine::GameEngine;
+/// # let engine = GameEngine::new(30, 10);
+/// for (i, player) in engine.get_players().iter().enumerate() {
+///     println!("player {}: {}", i + 1, player.get_score());
+/// }
+/// ```
+pub struct Player {
+    snake: Snake,
+    heading: Direction,
+    score: u16,
+    decay: f32,
+    crashed: bool
+}
+
+#[derive(Debug)]
+/// Represents the state of a game of Snake, without any terminal attached.
+///
+/// The `GameEngine` owns the board and everything on it, and advances it one tick at a time with
+/// `step`. It doesn't render, read keys or measure time, so it can be driven by a test, an AI or a
+/// headless simulation just as well as by the terminal `Game`, which is only one frontend for it.
+/// Everything that happened during a step is reported as `GameEvent`s, so a frontend can add effects
+/// without knowing the rules.
+///
+/// # Fields
+/// - `width`: The width of the board.
+/// - `height`: The height of the board.
+/// - `random`: The source of the engine's random decisions, such as where food is placed.
+/// - `players`: The players and their snakes, with the first one steered by the arrow keys and the second
+///   one (if any) by WASD.
+/// - `foods`: The food on the board: the normal food, and every now and then bonus or shrink food.
+/// - `food_placed`: The tick the normal food was placed in.
+/// - `fresh_food`: Whether food is worth more the sooner it's eaten.
+/// - `bug`: The bonus bug crawling along the edge of the board (if any).
+/// - `bug_countdown`: The number of ticks before the next bug appears.
+/// - `coin`: The position of the coin (if any).
+/// - `coins`: The number of coins collected.
+/// - `power_up`: The position and kind of the power-up on the board (if any).
+/// - `power_up_placed`: The tick the power-up was placed in.
+/// - `effects`: The effects of the power-ups picked up that haven't worn off yet.
+/// - `obstacles`: The cells inside the board taken by walls, which the snake can't cross.
+/// - `blocked`: The same cells as `obstacles`, to check collisions quickly.
+/// - `speed`: The current speed level, which goes up with every food that scores unless it's fixed.
+/// - `fixed_speed`: Whether the speed stays the same for the whole game.
+/// - `food_speeds_up`: Whether food that scores raises the speed, rather than something outside the engine.
+/// - `strict`: Whether the head may never move onto the cell the tail is leaving.
+/// - `hardcore`: Whether the score decays over time.
+/// - `bounce`: Whether the snake turns along a wall it runs into instead of crashing.
+/// - `wrap_around`: Whether the snake leaves the board on one side and comes back on the other.
+/// - `forgiving`: Whether collisions only stop the snake instead of ending the game.
+/// - `spawn_rate`: How much more often than usual the bug, coins and power-ups show up.
+/// - `tick`: The number of steps taken.
+/// - `events`: The events of the last step.
+///
+/// # Methods
+/// ## `new`
+/// Creates a new `GameEngine` with the snake in the middle of the board, heading in a random direction,
+/// and the first food placed.
+///
+/// ### Parameters
+/// - `width`: The width of the board, at least `MIN_BOARD_SIZE`.
+/// - `height`: The height of the board, at least `MIN_BOARD_SIZE`.
+///
+/// ## `with_random`
+/// Creates a new `GameEngine` that takes its random decisions from the given source.
+///
+/// ### Parameters
+/// - `width`: The width of the board.
+/// - `height`: The height of the board.
+/// - `random`: The `RandomSource` deciding the starting direction, food positions and other random events.
+///
+/// ## `reset`
+/// Starts a new game on the same board, with a fresh snake (or two), new food and the score, coins and
+/// effects cleared. The rules, the obstacles and the speed are kept, so a restart plays like the game
+/// before it; set the speed again to start over at the starting speed.
+///
+/// ## `set_strict`
+/// Enables or disables strict rules, in which the head may never move onto the cell the tail is leaving.
+///
+/// ### Parameters
+/// - `enabled`: Whether the rule applies.
+///
+/// ## `set_hardcore`
+/// Enables or disables hardcore mode, in which the score decays over time.
+///
+/// ### Parameters
+/// - `enabled`: Whether the rule applies.
+///
+/// ## `set_bounce`
+/// Enables or disables bouncing off walls, in which the snake turns along a wall it runs into at the
+/// cost of `BOUNCE_PENALTY` points, only crashing if neither way along the wall is free.
+///
+/// ### Parameters
+/// - `enabled`: Whether the rule applies.
+///
+/// ## `set_wrap_around`
+/// Enables or disables wrapping around, in which there are no walls and the snake moving off one side of
+/// the board comes back on the opposite side.
+///
+/// ### Parameters
+/// - `enabled`: Whether the rule applies.
+///
+/// ## `set_forgiving`
+/// Enables or disables forgiving collisions, in which the snake stops in front of an obstacle instead
+/// of crashing into it, as in practice and god mode.
+///
+/// ### Parameters
+/// - `enabled`: Whether the rule applies.
+///
+/// ## `set_fresh_food`
+/// Enables or disables fresh food scoring, in which food is worth `FRESH_FOOD_POINTS` and loses a point
+/// every `FRESH_FOOD_DECAY` ticks it stays on the board, down to a single point.
+///
+/// ### Parameters
+/// - `enabled`: Whether the rule applies.
+///
+/// ## `set_speed`
+/// Sets the current speed, which still goes up with every food that scores unless it's fixed.
+///
+/// ### Parameters
+/// - `speed`: The speed, from 1 (slowest) to `MAX_SPEED`.
+///
+/// ## `set_fixed_speed`
+/// Locks the speed for the whole game, or lets it increase with the score again.
+///
+/// ### Parameters
+/// - `speed`: The speed to lock the game at, from 1 (slowest) to `MAX_SPEED`, or `None` to let it increase.
+///
+/// ## `set_food_speeds_up`
+/// Lets food that scores raise the speed, or leaves raising it to the frontend, e.g. with the play time.
+///
+/// ### Parameters
+/// - `enabled`: Whether eating raises the speed.
+///
+/// ## `set_spawn_rate`
+/// Scales how often the bug, coins and power-ups show up, e.g. for adaptive difficulty. The rate
+/// carries over to the next game.
+///
+/// ### Parameters
+/// - `rate`: The factor the chances are multiplied and the delay before the next bug is divided by,
+///   with `1.0` for the usual rate.
+///
+/// ## `set_two_players`
+/// Adds a second snake for two players on one keyboard, or takes it off the board again. Both snakes
+/// start heading up, `PLAYER_OFFSET` columns to the right and left of the middle of the board. The
+/// snakes can't cross each other, and the game ends as soon as one of them crashes.
+///
+/// ### Parameters
+/// - `enabled`: Whether two players are playing.
+///
+/// ## `set_obstacles`
+/// Puts walls on the board, e.g. the layout of a `Level`. Cells taken by a snake are left free, and
+/// food on one of the walls is removed, with the normal food placed elsewhere.
+///
+/// ### Parameters
+/// - `obstacles`: The cells to put walls on.
+///
+/// ## `set_random`
+/// Replaces the source of the engine's random decisions, e.g. to reseed it.
+///
+/// ### Parameters
+/// - `random`: The new `RandomSource`.
+///
+/// ## `get_random`
+/// Returns the source of the engine's random decisions, so a frontend's own random decisions are
+/// reproduced along with the engine's.
+///
+/// ## `get_width`
+/// Returns the width of the board.
+///
+/// ## `get_height`
+/// Returns the height of the board.
+///
+/// ## `get_snake`
+/// Returns the first player's snake.
+///
+/// ## `get_players`
+/// Returns the players, in order.
+///
+/// ## `get_food`
+/// Returns the position of the normal food (if any).
+///
+/// ## `get_foods`
+/// Returns all the food on the board.
+///
+/// ## `get_bug`
+/// Returns the bonus bug (if any).
+///
+/// ## `get_coin`
+/// Returns the position of the coin (if any).
+///
+/// ## `get_coins`
+/// Returns the number of coins collected.
+///
+/// ## `get_power_up`
+/// Returns the position and kind of the power-up on the board (if any).
+///
+/// ## `get_effects`
+/// Returns the active effects of power-ups, in the order they were picked up.
+///
+/// ## `has_effect`
+/// Checks if the effect of a power-up is active.
+///
+/// ### Parameters
+/// - `power_up`: The power-up to check.
+///
+/// ## `get_obstacles`
+/// Returns the cells taken by walls inside the board.
+///
+/// ## `get_score`
+/// Returns the first player's score.
+///
+/// ## `get_winner`
+/// Decides who won a two player game. A player whose snake is the only one still standing wins; if
+/// nobody crashed, the highest score wins.
+///
+/// ### Returns
+/// The index of the winning player, or `None` for a draw.
+///
+/// ## `get_speed`
+/// Returns the current speed.
+///
+/// ## `get_tick`
+/// Returns the number of steps taken.
+///
+/// ## `is_fixed_speed`
+/// Checks if the speed is locked for the whole game.
+///
+/// ## `is_strict`
+/// Checks if strict rules apply.
+///
+/// ## `is_hardcore`
+/// Checks if the score decays over time.
+///
+/// ## `is_wrap_around`
+/// Checks if the snake comes back on the other side of the board instead of hitting the walls.
+///
+/// ## `is_fresh_food`
+/// Checks if food is scored by its freshness.
+///
+/// ## `is_bounce`
+/// Checks if the snake turns along the walls it runs into.
+///
+/// ## `get_events`
+/// Returns the events of the last step, in the order they happened.
+///
+/// ## `food_value`
+/// Returns the number of points the normal food is worth if it were eaten now.
+///
+/// ## `is_occupied`
+/// Checks if a cell is taken by a snake, an obstacle, the food, the bug, the coin or the power-up.
+///
+/// ### Parameters
+/// - `point`: A reference to a `Point` to check.
+///
+/// ### Returns
+/// `true` if the cell holds a gameplay element, otherwise `false`.
+///
+/// ## `is_obstacle`
+/// Checks if a cell is taken by an obstacle.
+///
+/// ### Parameters
+/// - `point`: A reference to a `Point` to check.
+///
+/// ### Returns
+/// `true` if the cell holds a wall, otherwise `false`.
+///
+/// ## `place_food`
+/// Randomly places the normal food on the grid in a location that does not overlap with a snake or an obstacle.
+///
+/// ## `set_food`
+/// Moves the normal food to a cell, starting its freshness over.
+///
+/// ### Parameters
+/// - `point`: The `Point` to place the food on.
+///
+/// ## `spawn_food_ahead`
+/// Places food on the cell right in front of the first snake's head, if that cell is free.
+///
+/// ## `grow_snake`
+/// Lets the first snake grow a number of segments, one on each of its next moves.
+///
+/// ### Parameters
+/// - `segments`: The number of segments to grow.
+///
+/// ## `teleport`
+/// Moves the whole first snake, keeping its shape, so that its head ends up on a cell, as long as the
+/// whole snake fits on the board there without overlapping an obstacle or the other snake.
+///
+/// ### Parameters
+/// - `head`: The new position of the snake's head.
+///
+/// ### Returns
+/// `true` if the snake was moved, otherwise `false`.
+///
+/// ## `turn`
+/// Turns the first snake, unless it would turn around or keep going the way it moved on the last step.
+///
+/// ### Parameters
+/// - `towards`: The direction to turn to.
+///
+/// ### Returns
+/// `true` if the snake turned, otherwise `false`.
+///
+/// ## `turn_player`
+/// Turns a player's snake, unless it would turn around or keep going the way it moved on the last step.
+///
+/// ### Parameters
+/// - `player`: The index of the player.
+/// - `towards`: The direction to turn to.
+///
+/// ### Returns
+/// `true` if the snake turned, otherwise `false`.
+///
+/// ## `apply`
+/// Applies a command right away, without advancing the game: turns, and spawning food, growing and
+/// shrinking for debugging. Other commands are for the frontend and are ignored.
+///
+/// ### Parameters
+/// - `command`: The command to apply.
+///
+/// ## `step`
+/// Applies a command (if any) and advances the game by one tick, in which the snakes move, eat,
+/// catch the bug and collect coins, or run into something.
+///
+/// ### Parameters
+/// - `command`: The command to apply before moving, with `Command::Quit` ending the game right away.
+///
+/// ### Returns
+/// The `GameStatus` after the step.
+///
+/// ## `decay_score`
+/// Lowers every player's score by the amount it decayed during the last tick in hardcore mode.
+///
+/// ### Parameters
+/// - `elapsed`: The duration of the last tick.
+///
+/// ## `add_points`
+/// Awards points to every player for something other than eating, like staying alive in survival mode.
+/// The points aren't doubled by the double points power-up and don't raise the speed.
+///
+/// ### Parameters
+/// - `points`: The points every player gets.
+///
+/// ## `expire_effects`
+/// Counts down the time left on the active effects, removing the ones that wore off. Effects last a
+/// fixed amount of time rather than a number of ticks, so the frontend passes the duration of every tick.
+///
+/// ### Parameters
+/// - `elapsed`: The duration of the last tick.
+///
+/// ## `describe`
+/// Describes the state of the game in a few lines of text, for the crash report.
+///
+/// ## `clear_snakes_surroundings`
+/// Removes the obstacles under the snakes and the food under the snakes or obstacles, placing the
+/// normal food elsewhere if it was removed.
+///
+/// ## `advance`
+/// Moves every snake by one cell unless one of them collides, and lets them eat whatever is on their
+/// new cells. Whether a snake collides is decided for all of them before any of them moves.
+///
+/// ### Returns
+/// The `GameStatus` after the move.
+///
+/// ## `move_player`
+/// Moves a player's snake by one cell and lets it eat whatever is on its new cell.
+///
+/// ### Parameters
+/// - `player`: The index of the player.
+///
+/// ## `eat`
+/// Lets a snake eat a piece of food: it grows or shrinks, the points are added to the player's score
+/// and the normal food is placed again. Food that scores also raises the speed a level, up to `MAX_SPEED`.
+///
+/// ### Parameters
+/// - `player`: The index of the player.
+/// - `food`: The `Food` the snake's head is on, already taken off the board.
+///
+/// ## `update_special_food`
+/// Removes bonus and shrink food that expired, and occasionally places a new one.
+///
+/// ## `award`
+/// Adds points to a player's score, doubled while the double points effect is active.
+///
+/// ### Parameters
+/// - `player`: The index of the player.
+/// - `points`: The points earned.
+///
+/// ### Returns
+/// The points actually added to the score.
+///
+/// ## `heads`
+/// Returns the positions of the snakes' heads, in the order of the players.
+///
+/// ## `update_power_up`
+/// Picks up the power-up when a snake's head is on it, activating its effect or starting it over,
+/// removes it when it's been on the board too long, and occasionally places a new one in single player games.
+///
+/// ## `update_bug`
+/// Spawns, moves and despawns the bonus bug, awarding its points to the player whose snake catches it.
+///
+/// ## `update_coin`
+/// Collects the coin when a snake's head is on it, and occasionally places a new one.
+///
+/// ## `is_blocked`
+/// Checks if a player's snake will run into a wall, itself, an obstacle or the other snake on the next move.
+///
+/// ### Parameters
+/// - `player`: The index of the player.
+///
+/// ### Returns
+/// `true` if the snake can't move on, otherwise `false`.
+///
+/// ## `has_collidated_with_wall`
+/// Checks if a player's snake's head has collided with the wall, which never happens when wrapping around.
+///
+/// ### Parameters
+/// - `player`: The index of the player.
+///
+/// ### Returns
+/// `true` if the snake has collided with a wall, otherwise `false`.
+///
+/// ## `is_facing_edge`
+/// Checks if a player's snake's head is on the edge of the board and heading off it.
+///
+/// ### Parameters
+/// - `player`: The index of the player.
+///
+/// ### Returns
+/// `true` if the next move would leave the board, otherwise `false`.
+///
+/// ## `next_head_point`
+/// Returns the cell a player's snake's head moves onto next, which is on the opposite side of the board
+/// if the snake is wrapping around an edge.
+///
+/// ### Parameters
+/// - `player`: The index of the player.
+///
+/// ### Returns
+/// The `Point` in front of the snake's head.
+///
+/// ## `has_hit_obstacle`
+/// Checks if a player's snake's head will run into an obstacle on the next move.
+///
+/// ### Parameters
+/// - `player`: The index of the player.
+///
+/// ### Returns
+/// `true` if the cell in front of the head is taken by an obstacle, otherwise `false`.
+///
+/// ## `has_hit_rival`
+/// Checks if a player's snake's head will run into the other snake on the next move. Moving onto
+/// the same cell as the other snake's head blocks both snakes, even if it's the cell a tail is leaving.
+///
+/// ### Parameters
+/// - `player`: The index of the player.
+///
+/// ### Returns
+/// `true` if the snake will collide with the other snake, otherwise `false`.
+///
+/// ## `bounce_off_wall`
+/// Turns a player's snake along the wall it's about to run into, preferring a clockwise turn, as long as
+/// the turn doesn't lead into another wall, an obstacle or a snake. Costs `BOUNCE_PENALTY` points.
+///
+/// ### Parameters
+/// - `player`: The index of the player.
+///
+/// ## `has_bitten_itself`
+/// Checks if a player's snake's head will collide with its body on the next move. Unless strict rules
+/// apply, the head may follow onto the cell the tail is leaving, as long as the snake isn't growing. A
+/// ghost snake never bites itself.
+///
+/// ### Parameters
+/// - `player`: The index of the player.
+///
+/// ### Returns
+/// `true` if the snake has bitten itself, otherwise `false`.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::direction::Direction;
+/// # use snake_the_sequel::engine::{GameEngine, GameStatus};
+/// let mut engine = GameEngine::new(30, 10);
+/// engine.turn(Direction::Up);
+/// while engine.step(None) == GameStatus::Running {}
+/// println!("{}", engine.get_score());
+/// ```
+pub struct GameEngine {
+    width: u16,
+    height: u16,
+    random: Box<dyn RandomSource>,
+    players: Vec<Player>,
+    foods: Vec<Food>,
+    food_placed: u64,
+    fresh_food: bool,
+    bug: Option<Bug>,
+    bug_countdown: u16,
+    coin: Option<Point>,
+    coins: u16,
+    power_up: Option<(Point, PowerUp)>,
+    power_up_placed: u64,
+    effects: Vec<ActiveEffect>,
+    obstacles: Vec<Point>,
+    blocked: HashSet<Point>,
+    speed: u16,
+    fixed_speed: bool,
+    food_speeds_up: bool,
+    strict: bool,
+    hardcore: bool,
+    bounce: bool,
+    wrap_around: bool,
+    forgiving: bool,
+    spawn_rate: f32,
+    tick: u64,
+    events: Vec<GameEvent>
+}
+
+impl Player {
+    fn new(snake: Snake) -> Self {
+        let heading = snake.get_direction();
+        Self { snake, heading, score: 0, decay: 0.0, crashed: false }
+    }
+
+    pub fn get_snake(&self) -> &Snake {
+        &self.snake
+    }
+
+    pub fn get_score(&self) -> u16 {
+        self.score
+    }
+
+    pub fn has_crashed(&self) -> bool {
+        self.crashed
+    }
+}
+
+impl GameEngine {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self::with_random(width, height, Box::new(ThreadRandom))
+    }
+
+    pub fn with_random(width: u16, height: u16, random: Box<dyn RandomSource>) -> Self {
+        let mut engine = Self {
+            width,
+            height,
+            random,
+            players: Vec::new(),
+            foods: Vec::new(),
+            food_placed: 0,
+            fresh_food: false,
+            bug: None,
+            bug_countdown: 0,
+            coin: None,
+            coins: 0,
+            power_up: None,
+            power_up_placed: 0,
+            effects: Vec::new(),
+            obstacles: Vec::new(),
+            blocked: HashSet::new(),
+            speed: 1,
+            fixed_speed: false,
+            food_speeds_up: true,
+            strict: false,
+            hardcore: false,
+            bounce: false,
+            wrap_around: false,
+            forgiving: false,
+            spawn_rate: 1.0,
+            tick: 0,
+            events: Vec::new()
+        };
+        engine.reset();
+
+        engine
+    }
+
+    pub fn reset(&mut self) {
+        let two_players = self.players.len() > 1;
+        let direction = match self.random.gen_range(0, 4) {
+            0 => Direction::Up,
+            1 => Direction::Right,
+            2 => Direction::Down,
+            3 => Direction::Left,
+            _ => unreachable!()
+        };
+
+        self.players = vec![Player::new(Snake::new(Point::new(self.width / 2, self.height / 2), START_LENGTH, direction))];
+        self.bug_countdown = self.bug_delay();
+        self.bug = None;
+        self.foods.clear();
+        self.food_placed = 0;
+        self.coin = None;
+        self.coins = 0;
+        self.power_up = None;
+        self.power_up_placed = 0;
+        self.effects.clear();
+        self.tick = 0;
+        self.events.clear();
+        self.place_food();
+        self.set_two_players(two_players);
+    }
+
+    pub fn set_strict(&mut self, enabled: bool) {
+        self.strict = enabled;
+    }
+
+    pub fn set_hardcore(&mut self, enabled: bool) {
+        self.hardcore = enabled;
+    }
+
+    pub fn set_bounce(&mut self, enabled: bool) {
+        self.bounce = enabled;
+    }
+
+    pub fn set_wrap_around(&mut self, enabled: bool) {
+        self.wrap_around = enabled;
+    }
+
+    pub fn set_forgiving(&mut self, enabled: bool) {
+        self.forgiving = enabled;
+    }
+
+    pub fn set_fresh_food(&mut self, enabled: bool) {
+        self.fresh_food = enabled;
+    }
+
+    pub fn set_speed(&mut self, speed: u16) {
+        self.speed = speed;
+    }
+
+    pub fn set_fixed_speed(&mut self, speed: Option<u16>) {
+        self.fixed_speed = speed.is_some();
+        if let Some(speed) = speed {
+            self.speed = speed;
+        }
+    }
+
+    pub fn set_food_speeds_up(&mut self, enabled: bool) {
+        self.food_speeds_up = enabled;
+    }
+
+    pub fn set_spawn_rate(&mut self, rate: f32) {
+        self.spawn_rate = rate;
+    }
+
+    pub fn set_two_players(&mut self, enabled: bool) {
+        if !enabled {
+            self.players.truncate(1);
+            return;
+        }
+
+        // both snakes start close to the middle, which the levels keep free of obstacles
+        let (x, y) = (self.width / 2, self.height / 2);
+        self.players = vec![
+            Player::new(Snake::new(Point::new(x + PLAYER_OFFSET, y), START_LENGTH, Direction::Up)),
+            Player::new(Snake::new(Point::new(x - PLAYER_OFFSET, y), START_LENGTH, Direction::Up))
+        ];
+        self.clear_snakes_surroundings();
+    }
+
+    pub fn set_obstacles(&mut self, obstacles: Vec<Point>) {
+        self.obstacles = obstacles;
+        self.clear_snakes_surroundings();
+    }
+
+    pub fn set_random(&mut self, random: Box<dyn RandomSource>) {
+        self.random = random;
+    }
+
+    pub fn get_random(&mut self) -> &mut dyn RandomSource {
+        self.random.as_mut()
+    }
+
+    pub fn get_width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> u16 {
+        self.height
+    }
+
+    pub fn get_snake(&self) -> &Snake {
+        &self.players[0].snake
+    }
+
+    pub fn get_players(&self) -> &[Player] {
+        &self.players
+    }
+
+    pub fn get_food(&self) -> Option<Point> {
+        self.foods.iter().find(|food| food.get_kind() == FoodKind::Normal).map(Food::get_point)
+    }
+
+    pub fn get_foods(&self) -> &[Food] {
+        &self.foods
+    }
+
+    pub fn get_bug(&self) -> Option<&Bug> {
+        self.bug.as_ref()
+    }
+
+    pub fn get_coin(&self) -> Option<Point> {
+        self.coin
+    }
+
+    pub fn get_coins(&self) -> u16 {
+        self.coins
+    }
+
+    pub fn get_power_up(&self) -> Option<(Point, PowerUp)> {
+        self.power_up
+    }
+
+    pub fn get_effects(&self) -> &[ActiveEffect] {
+        &self.effects
+    }
+
+    pub fn has_effect(&self, power_up: PowerUp) -> bool {
+        self.effects.iter().any(|effect| effect.get_power_up() == power_up)
+    }
+
+    pub fn get_obstacles(&self) -> &[Point] {
+        &self.obstacles
+    }
+
+    pub fn get_score(&self) -> u16 {
+        self.players[0].score
+    }
+
+    pub fn get_winner(&self) -> Option<usize> {
+        let standing: Vec<usize> = (0..self.players.len()).filter(|&i| !self.players[i].crashed).collect();
+
+        match standing.len() {
+            0 => None,
+            1 => Some(standing[0]),
+            // nobody crashed, so the game was quit and the best score wins
+            _ => {
+                let best = standing.iter().map(|&i| self.players[i].score).max()?;
+                let leaders: Vec<usize> = standing.into_iter().filter(|&i| self.players[i].score == best).collect();
+                if leaders.len() == 1 { Some(leaders[0]) } else { None }
+            }
+        }
+    }
+
+    pub fn get_speed(&self) -> u16 {
+        self.speed
+    }
+
+    pub fn get_tick(&self) -> u64 {
+        self.tick
+    }
+
+    pub fn is_fixed_speed(&self) -> bool {
+        self.fixed_speed
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    pub fn is_wrap_around(&self) -> bool {
+        self.wrap_around
+    }
+
+    pub fn is_hardcore(&self) -> bool {
+        self.hardcore
+    }
+
+    pub fn is_fresh_food(&self) -> bool {
+        self.fresh_food
+    }
+
+    pub fn is_bounce(&self) -> bool {
+        self.bounce
+    }
+
+    pub fn get_events(&self) -> &[GameEvent] {
+        &self.events
+    }
+
+    pub fn food_value(&self) -> u16 {
+        if !self.fresh_food {
+            return 1;
+        }
+
+        let lost = ((self.tick - self.food_placed) / FRESH_FOOD_DECAY).min(FRESH_FOOD_POINTS as u64) as u16;
+        (FRESH_FOOD_POINTS - lost).max(1)
+    }
+
+    pub fn is_occupied(&self, point: &Point) -> bool {
+        self.players.iter().any(|player| player.snake.contains_point(point))
+            || self.blocked.contains(point)
+            || self.foods.iter().any(|food| food.get_point() == *point)
+            || self.bug.as_ref().is_some_and(|bug| bug.contains_point(point))
+            || self.coin == Some(*point)
+            || self.power_up.is_some_and(|(power_up_point, _)| power_up_point == *point)
+    }
+
+    pub fn is_obstacle(&self, point: &Point) -> bool {
+        self.blocked.contains(point)
+    }
+
+    pub fn place_food(&mut self) {
+        loop {
+            let random_x = self.random.gen_range(0, self.width);
+            let random_y = self.random.gen_range(0, self.height);
+
+            let point = Point::new(random_x, random_y);
+            if !self.is_occupied(&point) {
+                self.set_food(point);
+                break;
+            }
+        }
+    }
+
+    pub fn set_food(&mut self, point: Point) {
+        self.foods.retain(|food| food.get_kind() != FoodKind::Normal);
+        self.foods.push(Food::new(FoodKind::Normal, point, None));
+        self.food_placed = self.tick;
+    }
+
+    pub fn spawn_food_ahead(&mut self) {
+        if self.has_collidated_with_wall(0) {
+            return;
+        }
+
+        let point = self.next_head_point(0);
+        if !self.players.iter().any(|player| player.snake.contains_point(&point)) && !self.blocked.contains(&point) {
+            self.set_food(point);
+        }
+    }
+
+    pub fn grow_snake(&mut self, segments: u16) {
+        self.players[0].snake.grow(segments);
+    }
+
+    pub fn teleport(&mut self, head: Point) -> bool {
+        let snake = &self.players[0].snake;
+        let old_head = snake.get_head_point();
+        let fits = snake.get_body_points().iter().all(|point| {
+            let x = point.x as i32 + head.x as i32 - old_head.x as i32;
+            let y = point.y as i32 + head.y as i32 - old_head.y as i32;
+            if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+                return false;
+            }
+
+            let point = Point::new(x as u16, y as u16);
+            !self.blocked.contains(&point) && !self.players[1..].iter().any(|player| player.snake.contains_point(&point))
+        });
+
+        if fits {
+            self.players[0].snake.teleport(head);
+        }
+
+        fits
+    }
+
+    pub fn turn(&mut self, towards: Direction) -> bool {
+        self.turn_player(0, towards)
+    }
+
+    pub fn turn_player(&mut self, player: usize, towards: Direction) -> bool {
+        let player = match self.players.get_mut(player) {
+            Some(player) => player,
+            None => return false
+        };
+
+        if player.heading == towards || player.heading.opposite() == towards {
+            return false;
+        }
+
+        player.snake.set_direction(towards);
+        true
+    }
+
+    pub fn apply(&mut self, command: Command) {
+        match command {
+            Command::Turn(towards) => {
+                self.turn_player(0, towards);
+            }
+            Command::TurnPlayerTwo(towards) => {
+                self.turn_player(1, towards);
+            }
+            Command::SpawnFood => self.spawn_food_ahead(),
+            Command::Grow => self.players[0].snake.grow(1),
+            Command::Shrink => self.players[0].snake.shrink(),
+            _ => {}
+        }
+    }
+
+    pub fn step(&mut self, command: Option<Command>) -> GameStatus {
+        self.events.clear();
+
+        match command {
+            Some(Command::Quit) => return GameStatus::Over,
+            Some(command) => self.apply(command),
+            None => {}
+        }
+
+        let status = self.advance();
+        for player in self.players.iter_mut() {
+            player.heading = player.snake.get_direction();
+        }
+        self.tick += 1;
+
+        status
+    }
+
+    pub fn decay_score(&mut self, elapsed: Duration) {
+        if !self.hardcore {
+            return;
+        }
+
+        for player in self.players.iter_mut() {
+            let length = player.snake.len() as f32;
+            player.decay += elapsed.as_secs_f32() * length * DECAY_PER_SEGMENT;
+
+            let lost = player.decay.floor();
+            player.decay -= lost;
+            player.score = player.score.saturating_sub(lost as u16);
+        }
+    }
+
+    pub fn add_points(&mut self, points: u16) {
+        for player in self.players.iter_mut() {
+            player.score = player.score.saturating_add(points);
+        }
+    }
+
+    pub fn expire_effects(&mut self, elapsed: Duration) {
+        for effect in self.effects.iter_mut() {
+            effect.elapse(elapsed);
+        }
+        self.effects.retain(|effect| !effect.is_expired());
+    }
+
+    pub fn describe(&self) -> String {
+        let players: Vec<String> = self.players.iter()
+            .map(|player| format!("score {}, heading {:?}, body {:?}", player.score, player.snake.get_direction(), player.snake.get_body_points()))
+            .collect();
+
+        format!(
+            "tick {}, speed {}, board {}x{}\n{}\nfoods {:?}, bug {:?}, coin {:?}\npower-up {:?}, effects {:?}",
+            self.tick,
+            self.speed,
+            self.width,
+            self.height,
+            players.join("\n"),
+            self.foods.iter().map(|food| (food.get_kind(), food.get_point())).collect::<Vec<_>>(),
+            self.bug.as_ref().map(|bug| bug.get_points()),
+            self.coin,
+            self.power_up,
+            self.effects
+        )
+    }
+
+    fn clear_snakes_surroundings(&mut self) {
+        let players = &self.players;
+        self.obstacles.retain(|point| !players.iter().any(|player| player.snake.contains_point(point)));
+        self.blocked = self.obstacles.iter().copied().collect();
+
+        let (players, blocked) = (&self.players, &self.blocked);
+        self.foods.retain(|food| {
+            let point = food.get_point();
+            !blocked.contains(&point) && !players.iter().any(|player| player.snake.contains_point(&point))
+        });
+        if self.get_food().is_none() {
+            self.place_food();
+        }
+    }
+
+    fn advance(&mut self) -> GameStatus {
+        for player in 0..self.players.len() {
+            // obstacles are walls too, so the snake bounces off them as well
+            if self.bounce && (self.has_collidated_with_wall(player) || self.has_hit_obstacle(player)) {
+                self.bounce_off_wall(player);
+            }
+        }
+
+        // every snake checks its move before any of them moves, so it doesn't matter who goes first
+        let blocked: Vec<bool> = (0..self.players.len()).map(|player| self.is_blocked(player)).collect();
+
+        for (player, &blocked) in blocked.iter().enumerate() {
+            if blocked {
+                self.events.push(GameEvent::Collided(self.players[player].snake.get_head_point()));
+
+                // when collisions aren't fatal the snake just stops until it's turned away from the obstacle
+                self.players[player].crashed = !self.forgiving;
+            }
+        }
+
+        if self.players.iter().any(|player| player.crashed) {
+            return GameStatus::Over;
+        }
+        if blocked.iter().all(|&blocked| blocked) {
+            return GameStatus::Blocked;
+        }
+
+        for (player, &blocked) in blocked.iter().enumerate() {
+            if !blocked {
+                self.move_player(player);
+            }
+        }
+
+        self.update_special_food();
+        self.update_power_up();
+        self.update_bug();
+        self.update_coin();
+
+        GameStatus::Running
+    }
+
+    fn move_player(&mut self, player: usize) {
+        let head_point = self.next_head_point(player);
+        let snake = &mut self.players[player].snake;
+
+        let tail_point = snake.get_tail_point();
+        snake.slither(head_point);
+        if !snake.contains_point(&tail_point) {
+            self.events.push(GameEvent::LeftCell(tail_point));
+        }
+
+        if let Some(index) = self.foods.iter().position(|food| food.get_point() == head_point) {
+            let food = self.foods.remove(index);
+            self.eat(player, food);
+        }
+    }
+
+    fn eat(&mut self, player: usize, food: Food) {
+        let kind = food.get_kind();
+        let value = match kind {
+            FoodKind::Normal => self.food_value(),
+            kind => kind.get_points()
+        };
+
+        let snake = &mut self.players[player].snake;
+        snake.grow(kind.get_growth());
+        for _ in 0..kind.get_shrinkage() {
+            let tail_point = snake.get_tail_point();
+            snake.shrink();
+            if !snake.contains_point(&tail_point) {
+                self.events.push(GameEvent::LeftCell(tail_point));
+            }
+        }
+
+        if kind == FoodKind::Normal {
+            self.place_food();
+        }
+        let value = self.award(player, value);
+        self.events.push(GameEvent::AteFood(food.get_point(), kind, value));
+
+        // how much faster every level gets is up to the frontend's speed curve
+        if value > 0 && self.food_speeds_up && !self.fixed_speed {
+            self.speed = (self.speed + 1).min(MAX_SPEED);
+        }
+    }
+
+    fn award(&mut self, player: usize, points: u16) -> u16 {
+        let points = if self.has_effect(PowerUp::DoublePoints) { points.saturating_mul(2) } else { points };
+        let score = &mut self.players[player].score;
+        *score = score.saturating_add(points);
+
+        points
+    }
+
+    fn heads(&self) -> Vec<Point> {
+        self.players.iter().map(|player| player.snake.get_head_point()).collect()
+    }
+
+    fn update_power_up(&mut self) {
+        let heads = self.heads();
+
+        match self.power_up {
+            Some((point, power_up)) if heads.contains(&point) => {
+                self.power_up = None;
+                self.effects.retain(|effect| effect.get_power_up() != power_up);
+                self.effects.push(ActiveEffect::new(power_up));
+                self.events.push(GameEvent::CollectedPowerUp(point, power_up));
+            }
+            Some(_) if self.tick - self.power_up_placed >= POWER_UP_LIFETIME => self.power_up = None,
+            Some(_) => {}
+            // the effects would apply to both snakes, so power-ups are left out of two player games
+            None if self.players.len() == 1 => {
+                if !self.roll(POWER_UP_CHANCE) {
+                    return;
+                }
+
+                let power_up = PowerUp::ALL[self.random.gen_range(0, PowerUp::ALL.len() as u16) as usize];
+                let point = Point::new(self.random.gen_range(0, self.width), self.random.gen_range(0, self.height));
+                if !self.is_occupied(&point) {
+                    self.power_up = Some((point, power_up));
+                    self.power_up_placed = self.tick;
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn update_special_food(&mut self) {
+        let tick = self.tick;
+        self.foods.retain(|food| !food.is_expired(tick));
+
+        let placed = self.foods.iter().any(|food| food.get_kind() != FoodKind::Normal);
+        if !placed && self.random.gen_range(0, 100) < SPECIAL_FOOD_CHANCE {
+            let kind = if self.random.gen_range(0, 2) == 0 { FoodKind::Bonus } else { FoodKind::Shrink };
+            let point = Point::new(self.random.gen_range(0, self.width), self.random.gen_range(0, self.height));
+            if !self.is_occupied(&point) {
+                self.foods.push(Food::new(kind, point, Some(tick + SPECIAL_FOOD_LIFETIME)));
+            }
+        }
+    }
+
+    fn update_bug(&mut self) {
+        let heads = self.heads();
+
+        match self.bug.take() {
+            Some(mut bug) => {
+                // the snake can run into the bug, or the bug can crawl into the snake's mouth
+                let mut catcher = heads.iter().position(|head| bug.contains_point(head));
+                if catcher.is_none() {
+                    bug.crawl();
+                    catcher = heads.iter().position(|head| bug.contains_point(head));
+                }
+
+                if let Some(player) = catcher {
+                    let reward = self.award(player, bug.get_reward());
+                    self.events.push(GameEvent::CaughtBug(heads[player], reward));
+                }
+
+                if catcher.is_some() || bug.is_expired() {
+                    self.bug_countdown = self.bug_delay();
+                } else {
+                    self.bug = Some(bug);
+                }
+            }
+            None if self.bug_countdown > 0 => self.bug_countdown -= 1,
+            None => {
+                // if the chosen spot is taken, another one is tried on the next tick
+                let bug = Bug::spawn(self.width, self.height, self.random.as_mut());
+                if bug.get_points().iter().all(|point| !self.is_occupied(point)) {
+                    self.bug = Some(bug);
+                }
+            }
+        }
+    }
+
+    fn update_coin(&mut self) {
+        let heads = self.heads();
+
+        if let Some(point) = self.coin.filter(|coin| heads.contains(coin)) {
+            // coins are only counted, the snake doesn't grow from them
+            self.coin = None;
+            self.coins += 1;
+            self.events.push(GameEvent::CollectedCoin(point));
+        } else if self.coin.is_none() && self.roll(COIN_CHANCE) {
+            let point = Point::new(self.random.gen_range(0, self.width), self.random.gen_range(0, self.height));
+            if !self.is_occupied(&point) {
+                self.coin = Some(point);
+            }
+        }
+    }
+
+    // rolls for something to show up with a chance given in percent, scaled by the spawn rate
+    fn roll(&mut self, percent: u16) -> bool {
+        (self.random.gen_range(0, 1000) as f32) < percent as f32 * 10.0 * self.spawn_rate
+    }
+
+    // picks the number of ticks until the next bug appears, which a higher spawn rate shortens
+    fn bug_delay(&mut self) -> u16 {
+        (self.random.gen_range(BUG_MIN_DELAY, BUG_MAX_DELAY) as f32 / self.spawn_rate) as u16
+    }
+
+    fn is_blocked(&self, player: usize) -> bool {
+        self.has_collidated_with_wall(player)
+            || self.has_bitten_itself(player)
+            || self.has_hit_obstacle(player)
+            || self.has_hit_rival(player)
+    }
+
+    fn has_collidated_with_wall(&self, player: usize) -> bool {
+        !self.wrap_around && self.is_facing_edge(player)
+    }
+
+    fn is_facing_edge(&self, player: usize) -> bool {
+        let snake = &self.players[player].snake;
+        let head_point = snake.get_head_point();
+
+        match snake.get_direction() {
+            Direction::Up => head_point.y == 0,
+            Direction::Right => head_point.x == self.width - 1,
+            Direction::Down => head_point.y == self.height - 1,
+            Direction::Left => head_point.x == 0,
+        }
+    }
+
+    fn next_head_point(&self, player: usize) -> Point {
+        let snake = &self.players[player].snake;
+        let head_point = snake.get_head_point();
+
+        if !self.is_facing_edge(player) {
+            return head_point.transform(snake.get_direction(), 1);
+        }
+
+        // only reached when wrapping around, as the snake otherwise stops at the wall
+        match snake.get_direction() {
+            Direction::Up => Point::new(head_point.x, self.height - 1),
+            Direction::Right => Point::new(0, head_point.y),
+            Direction::Down => Point::new(head_point.x, 0),
+            Direction::Left => Point::new(self.width - 1, head_point.y),
+        }
+    }
+
+    fn has_hit_obstacle(&self, player: usize) -> bool {
+        !self.has_collidated_with_wall(player) && self.blocked.contains(&self.next_head_point(player))
+    }
+
+    fn has_hit_rival(&self, player: usize) -> bool {
+        if self.has_collidated_with_wall(player) {
+            return false;
+        }
+
+        let next_head_point = self.next_head_point(player);
+
+        (0..self.players.len()).filter(|&other| other != player).any(|other| {
+            // moving into the cell the other snake's head moves into as well is a head-on collision
+            self.players[other].snake.contains_point(&next_head_point)
+                || (!self.has_collidated_with_wall(other) && self.next_head_point(other) == next_head_point)
+        })
+    }
+
+    fn bounce_off_wall(&mut self, player: usize) {
+        let direction = self.players[player].snake.get_direction();
+
+        for turn in [direction.clockwise(), direction.counter_clockwise()] {
+            self.players[player].snake.set_direction(turn);
+            if !self.is_blocked(player) {
+                let score = &mut self.players[player].score;
+                *score = score.saturating_sub(BOUNCE_PENALTY);
+                return;
+            }
+        }
+
+        self.players[player].snake.set_direction(direction);
+    }
+
+    fn has_bitten_itself(&self, player: usize) -> bool {
+        if self.has_effect(PowerUp::Ghost) {
+            return false;
+        }
+
+        let snake = &self.players[player].snake;
+        let next_head_point = self.next_head_point(player);
+
+        // the tail moves out of the way in the same tick, unless the snake is growing or strict rules apply
+        let tail_leaves = !snake.is_digesting() && !self.strict;
+
+        snake.contains_point(&next_head_point)
+            && !(tail_leaves && next_head_point == snake.get_tail_point())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::SeededRandom;
+    use std::collections::VecDeque;
+
+    // hands out the scripted values in order, and then always the highest value of the range, so
+    // nothing that only appears by chance ever does
+    #[derive(Debug)]
+    struct ScriptedRandom {
+        values: VecDeque<u16>
+    }
+
+    impl RandomSource for ScriptedRandom {
+        fn gen_range(&mut self, low: u16, high: u16) -> u16 {
+            self.values.pop_front().map_or(high - 1, |value| value.clamp(low, high - 1))
+        }
+    }
+
+    // an engine on a 10x10 board with the snake in the middle heading right, the food at the given cell,
+    // and the values the following random calls return
+    fn scripted_engine(food: Point, values: &[u16]) -> GameEngine {
+        let mut script = vec![1, BUG_MAX_DELAY, food.x, food.y];
+        script.extend_from_slice(values);
+        GameEngine::with_random(10, 10, Box::new(ScriptedRandom { values: script.into() }))
+    }
+
+    // steps the engine without any input until it isn't running any more, returning the steps it ran for
+    fn steps_until_stopped(engine: &mut GameEngine) -> (u16, GameStatus) {
+        let mut steps = 1;
+        loop {
+            match engine.step(None) {
+                GameStatus::Running => steps += 1,
+                status => return (steps, status)
+            }
+        }
+    }
+
+    // a snake curled into a square, heading left, with its tail right in front of its head
+    fn curled_snake() -> Snake {
+        let mut snake = Snake::new(Point::new(4, 5), 1, Direction::Right);
+        snake.grow(3);
+        for point in [Point::new(4, 6), Point::new(5, 6), Point::new(5, 5)] {
+            snake.slither(point);
+        }
+        snake.set_direction(Direction::Left);
+        snake
+    }
+
+    // an engine on a 10x10 board with the curled snake as its only player
+    fn curled_engine() -> GameEngine {
+        let mut engine = GameEngine::with_random(10, 10, Box::new(SeededRandom::new(0)));
+        engine.players[0] = Player::new(curled_snake());
+        engine
+    }
+
+    #[test]
+    fn head_may_follow_the_tail() {
+        let engine = curled_engine();
+
+        assert_eq!(engine.next_head_point(0), engine.get_snake().get_tail_point());
+        assert!(!engine.has_bitten_itself(0));
+    }
+
+    #[test]
+    fn head_bites_the_body() {
+        let mut engine = curled_engine();
+        engine.players[0].snake.set_direction(Direction::Down);
+
+        assert!(engine.has_bitten_itself(0));
+    }
+
+    #[test]
+    fn tail_of_a_digesting_snake_stays_in_the_way() {
+        let mut engine = curled_engine();
+        engine.players[0].snake.grow(1);
+
+        assert!(engine.has_bitten_itself(0));
+    }
+
+    #[test]
+    fn strict_rules_keep_the_tail_in_the_way() {
+        let mut engine = curled_engine();
+        engine.set_strict(true);
+
+        assert!(engine.has_bitten_itself(0));
+    }
+
+    #[test]
+    fn crashes_into_the_wall() {
+        let mut engine = scripted_engine(Point::new(9, 9), &[]);
+        assert_eq!(engine.get_snake().get_head_point(), Point::new(5, 5));
+
+        assert_eq!(steps_until_stopped(&mut engine), (5, GameStatus::Over));
+        assert_eq!(engine.get_events(), [GameEvent::Collided(Point::new(9, 5))]);
+    }
+
+    #[test]
+    fn wraps_around_the_board() {
+        let mut engine = scripted_engine(Point::new(9, 9), &[]);
+        engine.set_wrap_around(true);
+
+        for _ in 0..5 {
+            assert_eq!(engine.step(None), GameStatus::Running);
+        }
+        assert_eq!(engine.get_snake().get_head_point(), Point::new(0, 5));
+    }
+
+    #[test]
+    fn crashes_into_itself() {
+        let mut engine = scripted_engine(Point::new(9, 9), &[]);
+        engine.players[0] = Player::new(curled_snake());
+
+        assert_eq!(engine.step(Some(Command::Turn(Direction::Down))), GameStatus::Over);
+        assert_eq!(engine.get_events(), [GameEvent::Collided(Point::new(5, 5))]);
+    }
+
+    #[test]
+    fn crashes_into_an_obstacle() {
+        let mut engine = scripted_engine(Point::new(9, 9), &[]);
+        engine.set_obstacles(vec![Point::new(7, 5)]);
+
+        assert_eq!(steps_until_stopped(&mut engine), (2, GameStatus::Over));
+        assert_eq!(engine.get_events(), [GameEvent::Collided(Point::new(6, 5))]);
+    }
+
+    #[test]
+    fn rivals_crash_head_on() {
+        let mut engine = scripted_engine(Point::new(9, 9), &[]);
+        engine.set_two_players(true);
+        engine.players[0] = Player::new(Snake::new(Point::new(4, 5), 3, Direction::Right));
+        engine.players[1] = Player::new(Snake::new(Point::new(6, 5), 3, Direction::Left));
+
+        assert_eq!(engine.step(None), GameStatus::Over);
+        assert!(engine.players.iter().all(|player| player.crashed));
+    }
+
+    #[test]
+    fn grows_over_the_next_moves() {
+        let mut engine = scripted_engine(Point::new(9, 9), &[]);
+        engine.grow_snake(3);
+
+        for length in [4, 5, 6, 6] {
+            engine.step(None);
+            assert_eq!(engine.get_snake().len(), length);
+        }
+    }
+
+    #[test]
+    fn eating_places_the_next_food() {
+        let mut engine = scripted_engine(Point::new(6, 5), &[2, 8]);
+
+        engine.step(None);
+        assert_eq!(engine.get_events(), [GameEvent::LeftCell(Point::new(3, 5)), GameEvent::AteFood(Point::new(6, 5), FoodKind::Normal, 1)]);
+        assert_eq!(engine.get_food(), Some(Point::new(2, 8)));
+        assert_eq!(engine.get_speed(), 2);
+
+        engine.step(None);
+        assert_eq!(engine.get_snake().len(), 4);
+    }
+
+    #[test]
+    fn shrink_food_takes_segments_off() {
+        let mut engine = scripted_engine(Point::new(9, 9), &[]);
+        engine.players[0] = Player::new(Snake::new(Point::new(5, 5), 6, Direction::Right));
+        engine.foods.push(Food::new(FoodKind::Shrink, Point::new(6, 5), Some(SPECIAL_FOOD_LIFETIME)));
+
+        engine.step(None);
+        assert_eq!(engine.get_snake().len(), 4);
+        assert_eq!(engine.get_snake().get_tail_point(), Point::new(3, 5));
+        assert_eq!(engine.get_score(), 0);
+    }
+
+    #[test]
+    fn score_stops_at_the_maximum() {
+        let mut engine = scripted_engine(Point::new(9, 9), &[]);
+        engine.effects.push(ActiveEffect::new(PowerUp::DoublePoints));
+        engine.players[0].score = u16::MAX - 1;
+
+        assert_eq!(engine.award(0, u16::MAX), u16::MAX);
+        assert_eq!(engine.get_score(), u16::MAX);
+    }
+}
@@ -0,0 +1,57 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crossterm::ErrorKind;
+
+#[derive(Debug)]
+/// Represents an error that stops the game.
+///
+/// The terminal is restored before a `GameError` reaches the caller, so it can simply be printed.
+///
+/// # Variants
+/// - `Terminal`: A terminal operation failed, like switching to raw mode, resizing the terminal or
+///   writing a frame to it.
+///
+/// # Example
+/// ```no_run
+/// # use std::io::stdout;
+/// # use snake_the_sequel::config::Config;
+/// # use snake_the_sequel::game::Game;
+/// if let Err(error) = Game::new(stdout(), &Config::default()).run() {
+///     eprintln!("{}", error);
+/// }
+/// ```
+pub enum GameError {
+    Terminal(ErrorKind)
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            // crossterm only says an IO error occurred, not which one
+            Self::Terminal(ErrorKind::IoError(error)) => write!(f, "Terminal error: {}", error),
+            Self::Terminal(error) => write!(f, "Terminal error: {}", error)
+        }
+    }
+}
+
+impl Error for GameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Terminal(error) => Some(error)
+        }
+    }
+}
+
+impl From<io::Error> for GameError {
+    fn from(error: io::Error) -> Self {
+        Self::Terminal(ErrorKind::IoError(error))
+    }
+}
+
+impl From<ErrorKind> for GameError {
+    fn from(error: ErrorKind) -> Self {
+        Self::Terminal(error)
+    }
+}
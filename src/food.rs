@@ -0,0 +1,132 @@
+use crate::points::Point;
+
+const BONUS_POINTS: u16 = 5;
+const SHRINK_SEGMENTS: u16 = 2;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents the kinds of food the snake can eat.
+///
+/// # Variants
+/// - `Normal`: The regular food, which is always on the board. Worth a point and grows the snake.
+/// - `Bonus`: Food that only stays on the board briefly. Worth `BONUS_POINTS` and grows the snake.
+/// - `Shrink`: Food that only stays on the board briefly. Worth nothing, but removes `SHRINK_SEGMENTS`
+///   segments from the snake.
+///
+/// # Methods
+/// ## `get_points`
+/// Returns the number of points the food is worth, before any freshness is taken into account.
+///
+/// ## `get_shrinkage`
+/// Returns the number of segments the snake loses when it eats the food.
+///
+/// ## `get_growth`
+/// Returns the number of segments the snake grows when it eats the food.
+///
+/// ## `grows`
+/// Checks if the snake grows when it eats the food.
+pub enum FoodKind {
+    Normal,
+    Bonus,
+    Shrink
+}
+
+impl FoodKind {
+    pub fn get_points(self) -> u16 {
+        match self {
+            Self::Normal => 1,
+            Self::Bonus => BONUS_POINTS,
+            Self::Shrink => 0
+        }
+    }
+
+    pub fn get_shrinkage(self) -> u16 {
+        match self {
+            Self::Shrink => SHRINK_SEGMENTS,
+            _ => 0
+        }
+    }
+
+    pub fn get_growth(self) -> u16 {
+        match self {
+            Self::Shrink => 0,
+            _ => 1
+        }
+    }
+
+    pub fn grows(self) -> bool {
+        self.get_growth() > 0
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents a piece of food on the board.
+///
+/// # Fields
+/// - `kind`: The kind of food, which decides what eating it does.
+/// - `point`: The cell the food is on.
+/// - `expires`: The tick the food disappears in if it isn't eaten, or `None` if it stays until it's eaten.
+///
+/// # Methods
+/// ## `new`
+/// Creates a new piece of `Food`.
+///
+/// ### Parameters
+/// - `kind`: The kind of food.
+/// - `point`: The cell to put the food on.
+/// - `expires`: The tick the food disappears in, or `None` if it stays until it's eaten.
+///
+/// ## `get_kind`
+/// Returns the kind of food.
+///
+/// ## `get_point`
+/// Returns the cell the food is on.
+///
+/// ## `get_expiry`
+/// Returns the tick the food disappears in (if any).
+///
+/// ## `is_expired`
+/// Checks if the food has disappeared.
+///
+/// ### Parameters
+/// - `tick`: The current tick.
+///
+/// ### Returns
+/// `true` if the food expires in or before the given tick, otherwise `false`.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::food::{Food, FoodKind};
+/// # use snake_the_sequel::points::Point;
+/// # let tick = 0;
+/// let food = Food::new(FoodKind::Bonus, Point::new(3, 4), Some(tick + 30));
+/// if food.is_expired(tick) {
+///     // remove it from the board
+/// }
+/// ```
+pub struct Food {
+    kind: FoodKind,
+    point: Point,
+    expires: Option<u64>
+}
+
+impl Food {
+    pub fn new(kind: FoodKind, point: Point, expires: Option<u64>) -> Self {
+        Self { kind, point, expires }
+    }
+
+    pub fn get_kind(&self) -> FoodKind {
+        self.kind
+    }
+
+    pub fn get_point(&self) -> Point {
+        self.point
+    }
+
+    pub fn get_expiry(&self) -> Option<u64> {
+        self.expires
+    }
+
+    pub fn is_expired(&self, tick: u64) -> bool {
+        self.expires.is_some_and(|expires| tick >= expires)
+    }
+}
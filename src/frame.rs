@@ -0,0 +1,215 @@
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+use crossterm::style::{Color, Print, SetForegroundColor};
+use std::io::Write;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents a single character cell of the terminal, as drawn by the game.
+///
+/// # Fields
+/// - `symbol`: The character shown in the cell.
+/// - `color`: The foreground color of the character.
+struct Cell {
+    symbol: char,
+    color: Color
+}
+
+const BLANK: Cell = Cell { symbol: ' ', color: Color::Reset };
+
+#[derive(Debug)]
+/// Represents the frame the game is drawing, before it's written to the terminal.
+///
+/// Every draw call only changes the cells of the buffer. When the frame is presented, it's compared to
+/// the frame presented before it and only the cells that changed are written, queued up and flushed
+/// at once, so the terminal never shows a half-drawn frame and unchanged cells aren't redrawn at all.
+/// Cells outside the buffer are ignored, just like the terminal would clip them.
+///
+/// # Fields
+/// - `columns`: The number of columns of the buffer.
+/// - `rows`: The number of rows of the buffer.
+/// - `origin`: The column and row of the terminal the top left cell of the buffer is written to.
+/// - `cells`: The cells of the frame being drawn, row by row.
+/// - `presented`: The cells of the frame on the terminal, or `None` where it's unknown and has to be redrawn.
+///
+/// # Methods
+/// ## `new`
+/// Creates an empty `FrameBuffer` of which every cell is drawn when it's first presented.
+///
+/// ### Parameters
+/// - `columns`: The number of columns of the buffer.
+/// - `rows`: The number of rows of the buffer.
+///
+/// ## `clear`
+/// Blanks every cell of the frame being drawn, without touching the terminal.
+///
+/// ## `invalidate`
+/// Forgets what the terminal shows, so the next frame is drawn in full, e.g. after the terminal was cleared.
+///
+/// ## `set_origin`
+/// Moves the frame to another position on the terminal, e.g. to center it, after which the next frame
+/// is drawn in full.
+///
+/// ### Parameters
+/// - `x`: The column of the terminal to write the first column of the buffer to.
+/// - `y`: The row of the terminal to write the first row of the buffer to.
+///
+/// ## `put`
+/// Draws a character in a cell.
+///
+/// ### Parameters
+/// - `x`: The column of the cell.
+/// - `y`: The row of the cell.
+/// - `symbol`: The character to draw.
+/// - `color`: The foreground color of the character.
+///
+/// ## `print`
+/// Draws a text from a cell onwards, one character per cell.
+///
+/// ### Parameters
+/// - `x`: The column of the first character.
+/// - `y`: The row of the text.
+/// - `text`: The text to draw.
+/// - `color`: The foreground color of the text.
+///
+/// ## `present`
+/// Writes the cells that changed since the last presented frame and flushes the writer.
+///
+/// ### Parameters
+/// - `out`: The writer to write to, usually the standard output.
+///
+/// ### Returns
+/// A `crossterm::Result` indicating whether the frame was written.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::frame::FrameBuffer;
+/// # use crossterm::style::Color;
+/// # use std::io::stdout;
+/// let mut buffer = FrameBuffer::new(33, 13);
+/// buffer.clear();
+/// buffer.print(0, 0, "#####", Color::DarkGrey);
+/// buffer.put(3, 4, '•', Color::White);
+/// buffer.present(&mut stdout()).unwrap();
+/// ```
+pub struct FrameBuffer {
+    columns: u16,
+    rows: u16,
+    origin: (u16, u16),
+    cells: Vec<Cell>,
+    presented: Vec<Option<Cell>>
+}
+
+impl FrameBuffer {
+    pub fn new(columns: u16, rows: u16) -> Self {
+        let size = columns as usize * rows as usize;
+
+        Self {
+            columns,
+            rows,
+            origin: (0, 0),
+            cells: vec![BLANK; size],
+            presented: vec![None; size]
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.fill(BLANK);
+    }
+
+    pub fn invalidate(&mut self) {
+        self.presented.fill(None);
+    }
+
+    pub fn set_origin(&mut self, x: u16, y: u16) {
+        self.origin = (x, y);
+        self.invalidate();
+    }
+
+    pub fn put(&mut self, x: u16, y: u16, symbol: char, color: Color) {
+        if x < self.columns && y < self.rows {
+            self.cells[y as usize * self.columns as usize + x as usize] = Cell { symbol, color };
+        }
+    }
+
+    pub fn print(&mut self, x: u16, y: u16, text: &str, color: Color) {
+        for (i, symbol) in text.chars().enumerate() {
+            self.put(x.saturating_add(i as u16), y, symbol, color);
+        }
+    }
+
+    pub fn present(&mut self, out: &mut impl Write) -> crossterm::Result<()> {
+        // the cursor moves along by itself while printing, so it's only moved to skip unchanged cells
+        let mut cursor = None;
+        let mut color = None;
+        let (dx, dy) = self.origin;
+
+        for (i, cell) in self.cells.iter().enumerate() {
+            if self.presented[i] == Some(*cell) {
+                continue;
+            }
+
+            let x = (i % self.columns as usize) as u16;
+            let y = (i / self.columns as usize) as u16;
+
+            if cursor != Some((x, y)) {
+                queue!(out, MoveTo(x + dx, y + dy))?;
+            }
+            if color != Some(cell.color) {
+                queue!(out, SetForegroundColor(cell.color))?;
+                color = Some(cell.color);
+            }
+            queue!(out, Print(cell.symbol))?;
+
+            cursor = Some((x + 1, y));
+            self.presented[i] = Some(*cell);
+        }
+
+        out.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the bytes written by presenting the buffer
+    fn present(buffer: &mut FrameBuffer) -> Vec<u8> {
+        let mut out = Vec::new();
+        buffer.present(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn presents_an_unchanged_frame_without_writing() {
+        let mut buffer = FrameBuffer::new(10, 4);
+        buffer.print(1, 1, "snake", Color::Green);
+
+        assert!(!present(&mut buffer).is_empty());
+        assert!(present(&mut buffer).is_empty());
+    }
+
+    #[test]
+    fn presents_only_the_changed_cell() {
+        let mut buffer = FrameBuffer::new(10, 4);
+        buffer.set_origin(2, 3);
+        buffer.print(1, 1, "snake", Color::Green);
+        present(&mut buffer);
+
+        buffer.put(4, 2, '•', Color::White);
+        let mut expected = Vec::new();
+        queue!(expected, MoveTo(6, 5), SetForegroundColor(Color::White), Print('•')).unwrap();
+
+        assert_eq!(present(&mut buffer), expected);
+        assert!(present(&mut buffer).is_empty());
+    }
+
+    #[test]
+    fn presents_the_whole_frame_after_invalidating() {
+        let mut buffer = FrameBuffer::new(10, 4);
+        let first = present(&mut buffer);
+
+        buffer.invalidate();
+        assert_eq!(present(&mut buffer), first);
+    }
+}
@@ -1,20 +1,83 @@
-use crate::snake::Snake;
 use crate::points::Point;
 use crate::direction::Direction;
 
-use std::io::Stdout;
-use crossterm::ExecutableCommand;
-use crossterm::terminal::{Clear, ClearType, size, SetSize, enable_raw_mode, disable_raw_mode};
-use crossterm::style::{SetForegroundColor, Print, ResetColor, Color};
-use std::time::{Duration, Instant};
-use crossterm::cursor::{Show, MoveTo, Hide};
-use crossterm::event::{poll, read, Event, KeyCode, KeyModifiers, KeyEvent};
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use crossterm::queue;
+use crossterm::cursor::MoveTo;
+use crossterm::style::{Color, Print};
+use crossterm::terminal::{Clear, ClearType};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use crossterm::event::{poll, read, Event, KeyCode, KeyEvent};
 use crate::command::Command;
-use rand::Rng;
+use crate::controls::{ControlScheme, ControlShuffler, DirectionMap, Layout};
+use crate::keymap::Keymap;
+use crate::config::{Colors, Config};
+use crate::profiler::FrameProfiler;
+use crate::adaptive::AdaptiveDifficulty;
+use crate::random::{RandomSource, SeededRandom, ThreadRandom};
+use crate::engine::{GameEngine, GameEvent, GameStatus, MAX_SPEED};
+use crate::speed::{Difficulty, SpeedCurve};
+use crate::modes::GameMode;
+use crate::theme::{Glyphs, Theme};
+use crate::console::ConsoleCommand;
+use crate::macros::InputMacro;
+use crate::crash;
+use crate::pathfinding;
+use crate::autopilot;
+use crate::rules::Rule;
+use crate::screenshot::Screenshot;
+use crate::scores::{self, HighScores, MAX_NAME_LENGTH};
+use crate::menu::{self, Menu, MenuItem};
+use crate::observer::GameObserver;
+use crate::svg::SvgSnapshot;
+use crate::frame::FrameBuffer;
+use crate::error::GameError;
+use crate::terminal::TerminalGuard;
+use crate::levels::Level;
+use crate::food::FoodKind;
+use crate::powerups::PowerUp;
+use crate::effects::{Ambience, AmbientLayer, Particles, ScreenShake, Toast, Trail};
+use log::{debug, info, trace};
 
-const MAX_INTERVAL: u16 = 700;
-const MIN_INTERVAL: u16 = 200;
-const MAX_SPEED: u16 = 20;
+const SHAKE_FRAME_INTERVAL: u64 = 60;
+const PASTE_GAP: Duration = Duration::from_millis(2); // keys arriving closer together than this are pasted, not typed
+const RESUME_COUNTDOWN: u64 = 3; // seconds counted down before the game resumes
+const FRAME_INTERVAL: Duration = Duration::from_millis(100); // time between redraws while waiting for the next tick
+const FOOD_PULSE_FRAMES: u64 = 3; // frames the food stays bright, and then dim, while pulsing
+const COIN_GLYPHS: [char; 4] = ['$', 'S', '|', 'S']; // frames of the coin's spin
+const POWER_UP_COLOR: Color = Color::Green;
+const GHOST_COLOR: Color = Color::Grey;
+const SLOW_MOTION_FACTOR: u32 = 2; // the tick interval is multiplied by this while slow motion is active
+const TURN_BUFFER_SIZE: usize = 3; // turns a player can press ahead of the snake
+const MILESTONE_LENGTH: usize = 10;
+const HUD_ROWS: u16 = 1; // rows reserved below the board for the status line
+const MENU_SIZE: (u16, u16) = (24, 9); // columns and rows of the board the menu box needs
+const ENDLESS_RULE_SCORE: u16 = 25; // points between new rules in endless mode
+const VISION_RANGE: i32 = 6; // cells visible straight ahead of the head with limited vision
+const VISION_RADIUS: i32 = 2; // cells visible in every direction around the head with limited vision
+const MILESTONE_BORDER_COLORS: [Color; 5] = [Color::DarkGrey, Color::DarkBlue, Color::DarkMagenta, Color::DarkRed, Color::DarkCyan];
+
+// the high score table and the rank the score got, if the run counts and the table could be updated
+type HighScoreUpdate = Option<io::Result<(HighScores, Option<usize>)>>;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents the screens `Game::run` moves between.
+///
+/// # Variants
+/// - `Menu`: The main menu, where a game is started and the mode and options are chosen.
+/// - `Playing`: A game being played.
+/// - `GameOver`: The game-over screen after a game ended.
+/// - `Quit`: Leaving the game, which restores the terminal and prints the summary.
+enum Screen {
+    Menu,
+    Playing,
+    GameOver,
+    Quit
+}
 
 #[derive(Debug)]
 /// Represents the game logic and manages the state of a terminal-based Snake game.
@@ -22,48 +85,452 @@ const MAX_SPEED: u16 = 20;
 /// The `Game` struct handles the initialization, rendering, and main game loop, including
 /// interactions with the snake, food, user input, and terminal UI.
 ///
+/// The UI is rendered to any `Write`, usually the standard output. Input is still read from the
+/// terminal and the terminal is still resized and put in raw mode, whichever writer is used.
+///
 /// # Fields
-/// - `stdout`: The standard output used to render the game's UI in the terminal.
-/// - `original_terminal_size`: The terminal size before starting the game.
-/// - `width`: The width of the game grid.
-/// - `height`: The height of the game grid.
-/// - `food`: The current position of the food on the grid (if any).
-/// - `snake`: The `Snake` instance representing the player's snake.
-/// - `speed`: The current speed of the game, which increases with the score.
-/// - `score`: The player's current score.
+/// - `stdout`: The writer the game's UI is rendered to, usually the standard output.
+/// - `width`: The width of the game grid, as in the engine.
+/// - `height`: The height of the game grid, as in the engine.
+/// - `engine`: The `GameEngine` holding the board, the snake and the score, which the game feeds input to and renders.
+/// - `profiler`: Collects frame timings, for the debug overlay and the profile printed by `main`.
+/// - `profiling`: Whether the frame timings are handed out with `get_profile` when the game ends.
+/// - `practice`: Whether collisions are harmless, letting the snake wait in front of obstacles instead.
+/// - `god`: Whether god mode is enabled, making collisions harmless and enabling the debug commands.
+/// - `keymap`: The bindings of keys to commands.
+/// - `controls`: The remapping applied to the direction keys before they reach the snake.
+/// - `shuffler`: Randomly remaps the direction keys at regular intervals when the chaos modifier is enabled.
+/// - `limited_vision`: Whether only the cells around and straight ahead of the snake's head are rendered.
+/// - `ambience`: The ambient effect rendered in the unoccupied background cells (if any).
+/// - `particles`: The particle bursts spawned where food was eaten.
+/// - `trail`: The fading trail of cells the snake recently vacated.
+/// - `dimmed`: Whether the board is drawn in dark grey because the game is paused.
+/// - `frame`: The number of frames rendered, which drives the animations of the food, bug and coin.
+/// - `play_time`: The time the current game has been played, not counting pauses, as shown in the HUD.
+/// - `offset`: The offset of the whole board within the frame, used to shake it.
+/// - `buffer`: The frame being drawn, of which only the changed cells are written to the terminal.
+/// - `screenshot_on_death`: Whether a screenshot of the board is saved when the snake dies.
+/// - `export_svg`: Whether the final board is exported as an SVG drawing when the game ends.
+/// - `hints`: Whether the shortest path to the food is shown in practice and god mode.
+/// - `coordinates`: Whether the debug overlay with row and column indices is shown.
+/// - `head_position`: Whether the position of the snake's head is shown.
+/// - `toast`: The notification currently shown on top of the board (if any).
+/// - `milestone_borders`: Whether the border changes color at every length milestone.
+/// - `milestones`: The number of length milestones reached.
+/// - `endless`: Whether endless mode is enabled, adding a new rule every `ENDLESS_RULE_SCORE` points.
+/// - `rules`: The rules endless mode has added so far.
+/// - `afk_timeout`: The time without input after which the game pauses itself (if any).
+/// - `last_input`: The moment the last key was pressed.
+/// - `recording`: The turns recorded so far when a macro is being recorded.
+/// - `playback`: The macro whose turns steer the snake instead of the keyboard (if any).
+/// - `autopilot`: Whether the built-in AI steers the snake instead of the keyboard, as in demo mode.
+/// - `turns`: The turns each player pressed that haven't been applied yet, of which every tick applies one.
+/// - `pending_events`: The events put aside while a burst of keys was drained, such as a resize, which
+///   are handled before any new input is read.
+/// - `speed_curve`: The `SpeedCurve` turning the speed into the time between ticks.
+/// - `mode`: The `GameMode` deciding how the game speeds up, scores and ends.
+/// - `adaptive`: Adjusts the speed to the player's recent performance when adaptive difficulty is enabled.
+/// - `high_scores`: The file the high score table of classic games is kept in (if any), next to which the
+///   other modes and games with rules that change the scoring keep theirs.
+/// - `menu`: Whether the main menu is shown before the first game and after every game.
+/// - `observers`: The `GameObserver`s following the games, called in the order they were added.
+/// - `theme`: The colors and characters the board is drawn with.
+/// - `colors`: The colors chosen in the config, replacing the game's own coloring of the snake, food and borders.
+/// - `config`: The `Config` the game was created with, which a restart goes back to.
+/// - `report`: The messages about the files saved during the games, or why they couldn't be saved, printed
+///   when the player quits.
 ///
 /// # Methods
 /// ## `new`
-/// Creates a new instance of the `Game`.
+/// Creates a new instance of the `Game`, with the board size, modes, keys and colors of a `Config`.
+/// The settings can still be changed with the setters before the game is run.
 /// 
 /// ### Parameters
-/// - `stdout`: The standard output used for terminal rendering.
-/// - `width`: The width of the game grid.
-/// - `height`: The height of the game grid.
+/// - `stdout`: The writer to render to, usually the standard output.
+/// - `config`: The settings of the game.
 /// 
 /// ### Returns
 /// A new instance of the `Game`.
 ///
 /// ### Example
 /// ```rust
+/// # use snake_the_sequel::config::Config;
+/// # use snake_the_sequel::game::Game;
 /// let stdout = std::io::stdout();
-/// let game = Game::new(stdout, 20, 15);
+/// let game = Game::new(stdout, &Config::default());
 /// ```
 ///
+/// ## `with_random`
+/// Creates a new instance of the `Game` that takes its random decisions from the given source.
+///
+/// ### Parameters
+/// - `stdout`: The writer to render to, usually the standard output.
+/// - `config`: The settings of the game.
+/// - `random`: The `RandomSource` deciding the starting direction, food positions and other random events.
+///
+/// ### Returns
+/// A new instance of the `Game`.
+///
+/// ## `set_profiling`
+/// Enables or disables the profile of the frame timings, which the caller can print when the game ends.
+/// The timings are always collected for the debug overlay.
+///
+/// ### Parameters
+/// - `enabled`: Whether the profile should be handed out.
+///
+/// ## `get_profile`
+/// Returns the `FrameProfiler` with the frame timings of every game played, if profiling is enabled.
+///
+/// ## `set_practice`
+/// Enables or disables practice mode, in which collisions don't end the game.
+///
+/// ### Parameters
+/// - `enabled`: Whether the game should be played in practice mode.
+///
+/// ## `set_strict`
+/// Enables or disables strict rules, under which moving onto the cell the tail is vacating is a bite.
+///
+/// ### Parameters
+/// - `enabled`: Whether strict rules should apply.
+///
+/// ## `set_hardcore`
+/// Enables or disables hardcore mode, in which the score slowly decays at a rate that scales with the
+/// snake's length.
+///
+/// ### Parameters
+/// - `enabled`: Whether the game should be played in hardcore mode.
+///
+/// ## `set_bounce`
+/// Enables or disables bouncing off walls, in which the snake turns along a wall it runs into at the
+/// cost of `BOUNCE_PENALTY` points, only crashing if neither way along the wall is free.
+///
+/// ### Parameters
+/// - `enabled`: Whether the snake should bounce off walls.
+///
+/// ## `set_wrap_around`
+/// Enables or disables wrapping around, in which there are no walls and the snake moving off one side of
+/// the board comes back on the opposite side.
+///
+/// ### Parameters
+/// - `enabled`: Whether the snake should wrap around the edges of the board.
+///
+/// ## `set_god_mode`
+/// Enables or disables god mode, in which collisions don't end the game and food can be spawned and
+/// the snake grown or shrunk on demand (F5, F6 and F7).
+///
+/// ### Parameters
+/// - `enabled`: Whether god mode should be enabled.
+///
+/// ## `set_keymap`
+/// Sets the bindings of keys to commands, such as the keys of a control scheme or rebound quit and
+/// pause keys.
+///
+/// ### Parameters
+/// - `keymap`: The `Keymap` to use.
+///
+/// ## `set_controls`
+/// Sets the remapping applied to the direction keys, such as mirrored controls.
+///
+/// ### Parameters
+/// - `controls`: The `DirectionMap` to apply to every turn.
+///
+/// ## `set_shuffled_controls`
+/// Enables or disables randomly remapping the direction keys every 20 seconds of play time, warning the
+/// player about the new mapping 3 seconds in advance.
+///
+/// ### Parameters
+/// - `enabled`: Whether the controls should be shuffled.
+///
+/// ## `set_limited_vision`
+/// Enables or disables limited vision, in which only the cells right around the snake's head and a line
+/// of cells straight ahead of it are rendered.
+///
+/// ### Parameters
+/// - `enabled`: Whether vision should be limited.
+///
+/// ## `set_screenshot_on_death`
+/// Enables or disables saving a PNG screenshot of the board when the snake dies.
+///
+/// ### Parameters
+/// - `enabled`: Whether a screenshot should be saved on death.
+///
+/// ## `set_svg_export`
+/// Enables or disables exporting the final board as an SVG drawing when the game ends.
+///
+/// ### Parameters
+/// - `enabled`: Whether the final board should be exported.
+///
+/// ## `set_endless`
+/// Enables or disables endless mode, in which a random challenge rule is added every `ENDLESS_RULE_SCORE` points.
+///
+/// ### Parameters
+/// - `enabled`: Whether the game should be played in endless mode.
+///
+/// ## `set_fresh_food`
+/// Enables or disables fresh food scoring, in which food starts out worth `FRESH_FOOD_POINTS` and loses
+/// a point every `FRESH_FOOD_DECAY` ticks it stays on the board, down to a single point.
+///
+/// ### Parameters
+/// - `enabled`: Whether food should be scored by its freshness.
+///
+/// ## `set_speed`
+/// Sets the speed the game starts at, which still increases with the score.
+///
+/// ### Parameters
+/// - `speed`: The speed to start at, from 1 (slowest) to `MAX_SPEED`.
+///
+/// ## `set_fixed_speed`
+/// Locks the speed of the game for the whole run, or lets it increase with the score again.
+///
+/// ### Parameters
+/// - `speed`: The speed to lock the game at, from 1 (slowest) to `MAX_SPEED`, or `None` to let it increase.
+///
+/// ## `set_speed_curve`
+/// Sets how the time between ticks shrinks as the speed goes up, e.g. the curve of a `Difficulty`.
+///
+/// ### Parameters
+/// - `curve`: The `SpeedCurve` to follow.
+///
+/// ## `set_mode`
+/// Selects the mode the game is played in. Survival starts at speed 1 and only the play time raises it,
+/// unless the speed is fixed.
+///
+/// ### Parameters
+/// - `mode`: The `GameMode` to play.
+///
+/// ## `set_adaptive_difficulty`
+/// Enables or disables adaptive difficulty, which quietly speeds the game up or slows it down depending
+/// on how well the player is doing, and makes the bug, coins and power-ups show up less or more often.
+///
+/// ### Parameters
+/// - `enabled`: Whether the speed and spawn rates should adapt to the player.
+///
+/// ## `set_afk_timeout`
+/// Sets the time without any input after which the game assumes the player walked away and pauses.
+///
+/// ### Parameters
+/// - `timeout`: The time without input, or `None` to never pause automatically.
+///
+/// ## `set_macro_recording`
+/// Starts or stops recording the turns of the run, which are saved to a macro file when the game ends.
+/// The game should be seeded with the seed of the macro for the recording to be replayable.
+///
+/// ### Parameters
+/// - `recording`: The `InputMacro` to record the turns into, or `None` to stop recording.
+///
+/// ## `set_macro_playback`
+/// Replays the turns of a macro instead of taking them from the keyboard. The game should be seeded
+/// with the seed of the macro.
+///
+/// ### Parameters
+/// - `playback`: The `InputMacro` to replay, or `None` to steer with the keyboard.
+///
+/// ## `set_autopilot`
+/// Lets the built-in AI steer the snake instead of the keyboard, as an attract mode or to stress-test
+/// the game. The direction keys are ignored, while the other keys keep working.
+///
+/// ### Parameters
+/// - `enabled`: Whether the autopilot steers the snake.
+///
+/// ## `set_high_scores`
+/// Sets the file the high score table is kept in. When a run that counts makes it into the table, the
+/// player is asked for a name before the game closes, and the table is printed with the other results.
+///
+/// ### Parameters
+/// - `path`: The path of the high score file, or `None` to not keep high scores.
+///
+/// ## `add_observer`
+/// Registers an observer, which is told about the ticks, the food eaten, the speed going up and the
+/// end of every game from then on.
+///
+/// ### Parameters
+/// - `observer`: The `GameObserver` to call.
+///
+/// ## `set_menu`
+/// Enables or disables the main menu. Without it, the game starts straight away and quitting a game
+/// leaves, as the menu is skipped anyway in demo mode and while a macro is recorded or replayed.
+///
+/// ### Parameters
+/// - `enabled`: Whether the main menu is shown.
+///
+/// ## `set_hints`
+/// Shows or hides the training hint, which marks the shortest path from the snake's head to the food
+/// that doesn't cross its body. The hint is only shown in practice and god mode.
+///
+/// ### Parameters
+/// - `enabled`: Whether the hint should be shown.
+///
+/// ## `set_coordinates`
+/// Shows or hides the debug overlay, which numbers the rows and columns along the borders. It can also
+/// be toggled during the game with F8.
+///
+/// ### Parameters
+/// - `enabled`: Whether the overlay should be shown.
+///
+/// ## `set_head_position`
+/// Shows or hides the position of the snake's head in the bottom left corner of the board. It can also
+/// be toggled during the game with F9.
+///
+/// ### Parameters
+/// - `enabled`: Whether the position should be shown.
+///
+/// ## `set_level`
+/// Lays out one of the built-in levels on the board, putting obstacles on it that end the game on
+/// contact and that food never spawns on.
+///
+/// ### Parameters
+/// - `level`: The `Level` to play.
+///
+/// ## `set_two_players`
+/// Enables or disables two players on one keyboard, the first one steering with the arrow keys and
+/// the second one with WASD, unless the keymap binds other keys to the second snake. The game ends as soon as either snake crashes, and the high score table
+/// is left alone.
+///
+/// ### Parameters
+/// - `enabled`: Whether two players are playing.
+///
+/// ## `set_theme`
+/// Selects the colors and characters the board is drawn with.
+///
+/// ### Parameters
+/// - `theme`: The `Theme` to draw with. The colors chosen in the config still win over its colors.
+///
+/// ## `set_ascii`
+/// Switches between drawing with plain ASCII characters and with the Unicode box-drawing characters.
+///
+/// ### Parameters
+/// - `enabled`: Whether to draw with ASCII characters only.
+///
+/// ## `set_ambience`
+/// Selects the ambient effect rendered behind the game.
+///
+/// ### Parameters
+/// - `ambience`: The `Ambience` to render, or `None` for a plain background.
+///
+/// ## `set_milestone_borders`
+/// Enables or disables changing the border color at every length milestone.
+///
+/// ### Parameters
+/// - `enabled`: Whether the border color should change.
+///
 /// ## `run`
-/// Starts the main game loop, handling user input, rendering, and game logic.
+/// Prepares the terminal and moves between the screens until the player quits: the main menu, a game,
+/// and the game-over screen following every game that ends in a crash or runs out of time, from which
+/// the game can be restarted or the menu reached without leaving the terminal UI. Quitting a game also
+/// goes back to the menu. The summary of the last game is printed once the terminal is restored.
+/// Whatever the writer is, this needs a real terminal, as it reads the keyboard and enters raw mode.
+/// If the game fails or panics, its state is recorded for the crash report on the way out.
+///
+/// ### Returns
+/// An error if a terminal operation failed, after the terminal was restored.
+///
+/// ## `show_screens`
+/// Moves between the menu, the games and the game-over screens until the player quits.
+///
+/// ### Returns
+/// Whether a game was played and the outcome of the last update of the high score table, or an error
+/// if a terminal operation failed.
+///
+/// ## `play`
+/// Runs the main game loop for a single game, handling user input, rendering, and game logic. Between
+/// ticks the board is redrawn every `FRAME_INTERVAL`, so the animations keep their pace at any speed.
+///
+/// ### Returns
+/// `true` if the game ended because a snake crashed or the time ran out, `false` if the player quit,
+/// or an error if the game couldn't be rendered.
 ///
-/// ## `place_food`
-/// Randomly places food on the grid in a location that does not overlap with the snake.
+/// ## `show_game_over`
+/// Shows the final score, the best high score and the speed reached in a box on top of the dimmed
+/// board, and waits for the player to restart with R, or go back to the menu (or quit without one) with
+/// Esc or the keys bound to quitting. `q` isn't checked by itself, as it steers on some layouts.
+///
+/// ### Parameters
+/// - `high_scores`: The outcome of updating the high score table, as returned by `update_high_scores`.
+/// - `can_restart`: Whether the game can be restarted, which isn't the case while a macro is recorded or replayed.
+///
+/// ### Returns
+/// The `Screen` the player chose, or an error if the screen couldn't be rendered.
+///
+/// ## `show_menu`
+/// Shows the main menu in a box on top of the board, moved through with the up and down arrow keys
+/// (or the keys steering up and down) and picked with Enter or space. The left and right arrow keys
+/// change the settings, and Esc or the keys bound to quitting go back from the options or quit.
+///
+/// ### Returns
+/// `Screen::Playing` when a new game is started, `Screen::Quit` when the player quits, or an error if
+/// the menu couldn't be rendered.
+///
+/// ## `show_high_scores`
+/// Shows the high score table of the selected mode, as many entries as fit on the board, until a key
+/// is pressed.
+///
+/// ### Returns
+/// An error if the table couldn't be rendered.
+///
+/// ## `menu_text`
+/// Lays out a menu as the text of its box: the title, and every entry with the current value of its
+/// setting, the selected one marked with `>`.
+///
+/// ### Parameters
+/// - `menu`: The `Menu` to lay out.
+///
+/// ## `change_setting`
+/// Switches a setting of the menu to its next or previous value.
+///
+/// ### Parameters
+/// - `item`: The entry of the setting. Entries that aren't settings are ignored.
+/// - `forward`: Whether to switch to the next value rather than the previous one.
+///
+/// ## `reset`
+/// Sets the game up for a new round: the engine starts a new game at the configured speed, the rules
+/// endless mode added are lifted again, and the effects, queued turns and notifications are cleared. The
+/// adaptive difficulty only starts counting anew, keeping what it learned about the player.
+///
+/// ## `update_mode`
+/// Applies the rules of the game mode for the time that passed: survival raises the speed, up to
+/// `MAX_SPEED` and unless it's fixed, and awards points for every interval of play time completed.
+///
+/// ### Parameters
+/// - `elapsed`: The duration of the last tick, which `play_time` already includes.
+///
+/// ## `is_out_of_time`
+/// Checks if the play time reached the time limit of the game mode, if it has one.
+///
+/// ## `food_color`
+/// Returns the color of the food, which pulses between bright and dim every `FOOD_PULSE_FRAMES`
+/// frames, and fades with its value when fresh food scoring is enabled, unless a color was chosen for it.
+///
+/// ## `update_effects`
+/// Advances the visual effects by one frame.
+///
+/// ## `update_rules`
+/// Adds a random rule that isn't in effect yet when endless mode reaches a new score threshold.
+///
+/// ## `has_rule`
+/// Checks if a rule is already in effect, either from endless mode or from the command line.
 ///
 /// ## `render`
-/// Updates the game UI, including the snake, food, and borders.
+/// Updates the game UI, including the snake, food, effects, and borders.
+///
+/// ### Returns
+/// An error if the frame couldn't be written to the terminal.
+///
+/// ## `present`
+/// Writes the cells of the frame buffer that changed since the last frame to the terminal.
+///
+/// ### Returns
+/// An error if the frame couldn't be written to the terminal.
 ///
 /// ## `prepare_ui`
-/// Configures the terminal for raw mode and resizes the display for the game.
+/// Configures the terminal for raw mode and resizes the display for the game, with `HUD_ROWS` extra rows
+/// below the board for the HUD.
+///
+/// ### Returns
+/// The `TerminalGuard` restoring the terminal when it's dropped, or an error if the terminal couldn't be set up.
 ///
 /// ## `calculate_interval`
-/// Calculates the delay between game updates based on the current speed.
+/// Calculates the delay between game updates from the speed curve at the current speed and, unless
+/// the speed is fixed, the adaptive difficulty. Slow motion multiplies it by `SLOW_MOTION_FACTOR`.
 ///
 /// ### Returns
 /// A `Duration` indicating the update interval.
@@ -77,329 +544,2279 @@ const MAX_SPEED: u16 = 20;
 /// ### Returns
 /// An `Option<Command>` indicating the action to be taken (e.g., quit or change direction).
 ///
-/// ## `has_collidated_with_wall`
-/// Checks if the snake's head has collided with the wall.
+/// ## `wait_for_player`
+/// Pauses the game with the board dimmed and a message on top until a key is pressed, then counts down
+/// before resuming. Used both when the player pauses the game and when they seem to be away. The
+/// board is redrawn when the terminal is resized in the meantime.
+///
+/// ### Parameters
+/// - `message`: The message shown on top of the board while paused.
+///
+/// ### Returns
+/// The `Duration` the game was paused for, including the countdown, or an error if the board couldn't
+/// be rendered.
+///
+/// ## `count_down`
+/// Counts down from `RESUME_COUNTDOWN` on top of the board, one second at a time, before the game resumes.
+///
+/// ### Returns
+/// An error if the board couldn't be rendered.
+///
+/// ## `resize`
+/// Redraws the board to fit the terminal after it was resized. When the terminal is too small for the
+/// board, the game is paused with a message until the terminal is large enough again, and then counts
+/// down before resuming.
+///
+/// ### Parameters
+/// - `cols`: The new number of columns of the terminal.
+/// - `rows`: The new number of rows of the terminal.
+///
+/// ### Returns
+/// The `Duration` the game was paused for, `None` if the player quit while it was paused, or an error
+/// if the board couldn't be rendered.
+///
+/// ## `fit_to_terminal`
+/// Clears the terminal and centers the board in it, or shows a "Terminal too small" message instead
+/// when the board, with its borders, doesn't fit.
+///
+/// ### Parameters
+/// - `cols`: The number of columns of the terminal.
+/// - `rows`: The number of rows of the terminal.
+///
+/// ### Returns
+/// `true` if the board fits and can be rendered, or an error if the terminal couldn't be written to.
+///
+/// ## `turn`
+/// Turns the snake, unless it would turn around or keep going the same way, and records the turn if
+/// a macro is being recorded.
+///
+/// ### Parameters
+/// - `towards`: The direction to turn to.
+///
+/// ## `queue_turn`
+/// Queues a turn pressed by a player, to be applied on the first tick that doesn't have a turn yet.
+/// The turn is checked against the turn queued before it, so quickly pressing up and left while
+/// heading right turns the snake around the corner over two ticks. Turns that wouldn't change the
+/// direction or would reverse it are dropped, as are turns beyond `TURN_BUFFER_SIZE`.
+///
+/// ### Parameters
+/// - `player`: The index of the player.
+/// - `towards`: The direction to turn to.
+///
+/// ## `handle_events`
+/// Plays the effects for what happened in the engine during the last step, like the particles where
+/// food was eaten and the trail behind the tail.
+///
+/// ## `change_speed`
+/// Raises or lowers the speed by one level, within `1` and `MAX_SPEED`, and shows the new speed.
+///
+/// ### Parameters
+/// - `faster`: Whether the speed should be raised rather than lowered.
+///
+/// ## `update_high_scores`
+/// Adds the score to the high score table if it makes it in, asking the player for a name. Practice,
+/// god mode and replayed runs don't count.
+///
+/// ### Returns
+/// `None` if the run doesn't count or no high scores are kept, otherwise the table and the rank the
+/// score got (if any), or an error if the file couldn't be read or written. The outer error is a
+/// `GameError` for when the name prompt couldn't be rendered.
+///
+/// ## `scoring_rules`
+/// Returns the names of the rules that change how the score adds up, `hardcore`, `fresh-food` and
+/// `bounce`, of which the enabled ones pick the high score table along with the mode.
+///
+/// ## `enter_name`
+/// Asks for the player's name in the bottom border, with the board dimmed behind a "NEW HIGH SCORE" box.
+/// Keys mashed at the end of the game are discarded first, and the prompt is redrawn when the terminal
+/// is resized, as long as the board still fits.
 ///
 /// ### Returns
-/// `true` if the snake has collided with a wall, otherwise `false`.
+/// The name, or `None` if the player skipped entering one with Esc, or an error if the prompt couldn't
+/// be rendered.
 ///
-/// ## `has_bitten_itself`
-/// Checks if the snake's head has collided with its body.
+/// ## `open_console`
+/// Opens the developer console in the bottom border and reads a command from it, freezing the game
+/// until the command is entered with Enter or the console is closed with Esc or the backtick.
 ///
 /// ### Returns
-/// `true` if the snake has bitten itself, otherwise `false`.
+/// An error if the console couldn't be rendered.
 ///
-/// ## `restore_ui`
-/// Restores the terminal to its original state after the game ends.
+/// ## `run_console_command`
+/// Executes a command entered in the developer console.
+///
+/// ### Parameters
+/// - `command`: The `ConsoleCommand` to execute.
+///
+/// ### Returns
+/// A message describing the result, or the reason the command couldn't be executed.
+///
+/// ## `discard_burst`
+/// Drains the keys that arrive right after a key press, which happens when text is pasted into the
+/// terminal or input floods in, so that they aren't played as moves (or as `q`). Any other event, like
+/// a resize, is put aside in `pending_events`. A key pressed on its own only reaches the game once no
+/// burst followed it, and in two player games two keys pressed at the same moment both do.
+///
+/// ### Returns
+/// The keys drained, which the game ignores unless there are fewer of them than players.
+///
+/// ## `read_event`
+/// Waits for the next event, handing out the ones put aside in `pending_events` first.
+///
+/// ### Returns
+/// The event, or an error if the terminal couldn't be read.
+///
+/// ## `notify`
+/// Calls every observer in turn.
+///
+/// ### Parameters
+/// - `callback`: Calls the callback of an observer, which gets the engine to pass to it.
+///
+/// ## `reach_milestone`
+/// Announces that the snake's length reached a milestone.
+///
+/// ### Parameters
+/// - `length`: The length that was reached.
+///
+/// ## `save_screenshot`
+/// Renders the current board offscreen and saves it as a PNG file in the working directory.
+///
+/// ### Returns
+/// The name of the saved file, or the error that prevented saving it.
+///
+/// ## `save_macro`
+/// Saves the recorded turns as a macro file in the working directory.
+///
+/// ### Returns
+/// The name of the saved file, or the error that prevented saving it. Returns `None` when nothing is
+/// being recorded.
+///
+/// ## `save_svg`
+/// Saves the current board as an SVG drawing in the working directory.
+///
+/// ### Returns
+/// The name of the saved file, or the error that prevented saving it.
+///
+/// ## `export_file_name`
+/// Returns a file name for an export that isn't taken yet, based on the current time in milliseconds
+/// and numbered if another export was saved in the same millisecond.
+///
+/// ### Parameters
+/// - `extension`: The extension of the file.
+///
+/// ## `shake_screen`
+/// Briefly shakes the board by rendering it at slightly different offsets.
+///
+/// ### Returns
+/// An error if the board couldn't be rendered.
+///
+/// ## `snake_color`
+/// Returns the color of the snake, which is `GHOST_COLOR` while it's a ghost, and otherwise the chosen
+/// color or, if none was chosen, changes with the speed.
+///
+/// ## `player_color`
+/// Returns the color of a player's snake: `snake_color` for the first player and the theme's second
+/// for the second one.
+///
+/// ### Parameters
+/// - `player`: The index of the player.
+///
+/// ## `border_color`
+/// Returns the color of the borders, which is the chosen color if there is one, and otherwise changes
+/// with the milestones if enabled.
+///
+/// ## `shade`
+/// Returns the color a gameplay element on the board should be drawn in, which is dark grey while the
+/// board is dimmed.
+///
+/// ### Parameters
+/// - `color`: The color the element is normally drawn in.
+///
+/// ## `themed`
+/// Returns the character the theme draws a small dot with, for the dots the trail, the particles and
+/// the ambience produce, and any other character unchanged.
+///
+/// ### Parameters
+/// - `symbol`: The character to draw.
 ///
 /// ## `draw_snake`
-/// Renders the snake on the grid using color and symbols.
+/// Renders every player's snake on the grid using color and symbols.
 ///
 /// ## `draw_food`
-/// Renders the food on the grid.
+/// Renders the food on the grid, normal food as a pulsing dot, bonus food as a blue diamond and shrink
+/// food as a red ring.
+///
+/// ## `draw_bug`
+/// Renders the bonus bug on the grid, wriggling through the theme's bug glyphs.
+///
+/// ## `draw_coin`
+/// Renders the coin on the grid, spinning through `COIN_GLYPHS`.
+///
+/// ## `draw_power_up`
+/// Renders the power-up on the grid, as `~` for slow motion, `@` for ghost and `2` for double points.
 ///
 /// ## `draw_background`
-/// Clears the grid area of the game.
+/// Clears the unoccupied cells of the grid, drawing the ambient effect in them.
+///
+/// ## `draw_obstacles`
+/// Renders the obstacles of the level as walls inside the grid.
+///
+/// ## `draw_trail`
+/// Renders the fading trail behind the snake on the unoccupied cells of the grid.
+///
+/// ## `draw_hint`
+/// Renders the shortest path to the food as faint dots on the unoccupied cells of the grid, when the
+/// training hint is enabled.
+///
+/// ## `draw_particles`
+/// Renders the particle bursts on the unoccupied cells of the grid.
+///
+/// ## `is_visible`
+/// Checks if a cell should be rendered, which is always the case unless vision is limited.
+///
+/// ### Returns
+/// `true` if the contents of the cell should be drawn, otherwise `false`.
 ///
 /// ## `draw_borders`
 /// Draws the borders of the game grid using symbols.
 ///
-/// # Example
-/// ```rust
-/// let stdout = std::io::stdout();
-/// let mut game = Game::new(stdout, 20, 15);
-/// game.run();
-/// ```
-
-pub struct Game {
-    stdout: Stdout,
-    original_terminal_size: (u16, u16),
-    width: u16,
-    height: u16, 
-    food: Option<Point>,
-    snake: Snake,
-    speed: u16,
-    score: u16
-}
-
-impl Game {
-    pub fn new(stdout: Stdout, width: u16, height: u16) -> Self {
-        let original_terminal_size: (u16, u16) = size().unwrap();
+/// ## `draw_controls_warning`
+/// Renders the upcoming controls and a countdown in the bottom border before the controls are shuffled.
+///
+/// ## `draw_rules`
+/// Lists the rules added by endless mode in the bottom right of the border.
+///
+/// ## `draw_effects`
+/// Lists the active power-up effects and the seconds they have left in the top left of the border.
+///
+/// ## `draw_coordinates`
+/// Renders the last digit of every column index in the top border and of every row index in the left
+/// border.
+///
+/// ## `draw_profile`
+/// Renders the number of slow frames and the percentiles of the frame times, a line each, in the top
+/// right corner of the board when the debug overlay is shown, on top of everything else.
+///
+/// ## `draw_head_position`
+/// Renders the position of the snake's head in the bottom left corner of the board, on top of everything
+/// else, when it's enabled.
+///
+/// ## `draw_toast`
+/// Renders the current notification centered in the top border.
+///
+/// ## `draw_overlay`
+/// Renders a message in a framed box in the middle of the board, with every line of the message
+/// centered in the box.
+///
+/// ### Parameters
+/// - `text`: The message to show, which may span several lines.
+///
+/// ### Returns
+/// An error if the box couldn't be written to the terminal.
+///
+/// ## `draw_name_entry`
+/// Renders the name prompt for a new high score in the bottom border.
+///
+/// ### Parameters
+/// - `name`: The name typed so far.
+///
+/// ### Returns
+/// An error if the prompt couldn't be written to the terminal.
+///
+/// ## `draw_console`
+/// Renders the developer console prompt in the bottom border.
+///
+/// ### Parameters
+/// - `line`: The text typed into the console so far.
+///
+/// ### Returns
+/// An error if the prompt couldn't be written to the terminal.
+///
+/// ## `draw_coin_counter`
+/// Renders the number of coins collected in the bottom left of the border.
+///
+/// ## `draw_scoreboard`
+/// Renders the score of every player in their snake's color in the top right of the border, in two
+/// player games.
+///
+/// ## `draw_hud`
+/// Renders the status line below the board, with the score, the speed and the length of the snake on
+/// the left and the time played on the right, or the time left when the mode has a time limit. In two
+/// player games, the scores and lengths of both players are shown.
+///
+/// # Example
+/// ```no_run
+/// # use snake_the_sequel::config::Config;
+/// # use snake_the_sequel::game::Game;
+/// let stdout = std::io::stdout();
+/// let mut game = Game::new(stdout, &Config::default());
+/// game.run()?;
+/// # Ok::<(), snake_the_sequel::error::GameError>(())
+/// ```
+
+pub struct Game<W: Write> {
+    stdout: W,
+    width: u16,
+    height: u16, 
+    engine: GameEngine,
+    profiler: FrameProfiler,
+    profiling: bool,
+    practice: bool,
+    god: bool,
+    keymap: Keymap,
+    controls: DirectionMap,
+    shuffler: Option<ControlShuffler>,
+    limited_vision: bool,
+    ambience: Option<AmbientLayer>,
+    particles: Particles,
+    trail: Trail,
+    dimmed: bool,
+    frame: u64,
+    play_time: Duration,
+    offset: (u16, u16),
+    buffer: FrameBuffer,
+    screenshot_on_death: bool,
+    export_svg: bool,
+    hints: bool,
+    coordinates: bool,
+    head_position: bool,
+    toast: Option<Toast>,
+    milestone_borders: bool,
+    milestones: usize,
+    endless: bool,
+    rules: Vec<Rule>,
+    afk_timeout: Option<Duration>,
+    last_input: Instant,
+    recording: Option<InputMacro>,
+    playback: Option<InputMacro>,
+    autopilot: bool,
+    turns: [VecDeque<Direction>; 2],
+    pending_events: VecDeque<Event>,
+    speed_curve: SpeedCurve,
+    mode: GameMode,
+    adaptive: Option<AdaptiveDifficulty>,
+    high_scores: Option<PathBuf>,
+    menu: bool,
+    observers: Vec<Box<dyn GameObserver>>,
+    theme: Theme,
+    colors: Colors,
+    config: Config,
+    report: Vec<Result<String, String>>
+}
+
+impl<W: Write> Game<W> {
+    pub fn new(stdout: W, config: &Config) -> Self {
+        Self::with_random(stdout, config, Box::new(ThreadRandom))
+    }
+
+    pub fn with_random(stdout: W, config: &Config, random: Box<dyn RandomSource>) -> Self {
+        let (width, height) = (config.width, config.height);
+
+        let mut game = Self {
+            stdout,
+            width,
+            height,
+            engine: GameEngine::with_random(width, height, random),
+            profiler: FrameProfiler::new(),
+            profiling: false,
+            practice: false,
+            god: false,
+            keymap: Keymap::new(ControlScheme::Absolute, Layout::Qwerty),
+            controls: DirectionMap::identity(),
+            shuffler: None,
+            limited_vision: false,
+            ambience: None,
+            particles: Particles::new(width, height),
+            trail: Trail::new(),
+            dimmed: false,
+            frame: 0,
+            play_time: Duration::ZERO,
+            offset: (0, 0),
+            buffer: FrameBuffer::new(width + 3, height + 3 + HUD_ROWS),
+            screenshot_on_death: false,
+            export_svg: false,
+            hints: false,
+            coordinates: false,
+            head_position: false,
+            toast: None,
+            milestone_borders: false,
+            milestones: 0,
+            endless: false,
+            rules: Vec::new(),
+            afk_timeout: None,
+            last_input: Instant::now(),
+            recording: None,
+            playback: None,
+            autopilot: false,
+            turns: [VecDeque::new(), VecDeque::new()],
+            pending_events: VecDeque::new(),
+            speed_curve: config.speed_curve,
+            mode: GameMode::Classic,
+            adaptive: None,
+            high_scores: None,
+            menu: false,
+            observers: Vec::new(),
+            theme: Theme::default(),
+            colors: config.colors,
+            config: config.clone(),
+            report: Vec::new()
+        };
+
+        game.set_practice(config.practice);
+        game.set_strict(config.strict);
+        game.set_hardcore(config.hardcore);
+        game.set_god_mode(config.god);
+        game.set_ambience(config.ambience);
+        game.set_milestone_borders(config.milestone_borders);
+        game.set_limited_vision(config.limited_vision);
+        game.set_keymap(config.keymap());
+        game.set_controls(config.controls);
+        game.set_shuffled_controls(config.shuffle_controls);
+        game.set_screenshot_on_death(config.screenshot_on_death);
+        game.set_svg_export(config.export_svg);
+        game.set_endless(config.endless);
+        game.set_adaptive_difficulty(config.adaptive);
+        game.set_speed(config.speed);
+        game.set_fixed_speed(config.fixed_speed);
+        game.set_mode(config.mode);
+        game.set_wrap_around(config.wrap_around);
+        game.set_level(config.level);
+        game.set_two_players(config.players == 2);
+        game.set_fresh_food(config.fresh_food);
+        game.set_afk_timeout(Some(Duration::from_secs(config.afk_timeout)).filter(|timeout| !timeout.is_zero()));
+        game.set_bounce(config.bounce);
+        game.set_coordinates(config.coordinates);
+        game.set_head_position(config.head_position);
+        game.set_hints(config.hints);
+        game.set_autopilot(config.demo);
+        game.set_theme(config.theme);
+        game.set_ascii(config.ascii);
+        game.set_menu(config.menu);
+
+        game
+    }
+
+    pub fn set_profiling(&mut self, enabled: bool) {
+        self.profiling = enabled;
+    }
+
+    pub fn get_profile(&self) -> Option<&FrameProfiler> {
+        Some(&self.profiler).filter(|_| self.profiling)
+    }
+
+    pub fn set_practice(&mut self, enabled: bool) {
+        self.practice = enabled;
+        self.engine.set_forgiving(self.practice || self.god);
+    }
+
+    pub fn set_strict(&mut self, enabled: bool) {
+        self.engine.set_strict(enabled);
+    }
+
+    pub fn set_hardcore(&mut self, enabled: bool) {
+        self.engine.set_hardcore(enabled);
+    }
+
+    pub fn set_bounce(&mut self, enabled: bool) {
+        self.engine.set_bounce(enabled);
+    }
+
+    pub fn set_wrap_around(&mut self, enabled: bool) {
+        self.engine.set_wrap_around(enabled);
+    }
+
+    pub fn set_god_mode(&mut self, enabled: bool) {
+        self.god = enabled;
+        self.engine.set_forgiving(self.practice || self.god);
+    }
+
+    pub fn set_milestone_borders(&mut self, enabled: bool) {
+        self.milestone_borders = enabled;
+    }
+
+    pub fn set_keymap(&mut self, keymap: Keymap) {
+        self.keymap = keymap;
+    }
+
+    pub fn set_controls(&mut self, controls: DirectionMap) {
+        self.controls = controls;
+    }
+
+    pub fn set_shuffled_controls(&mut self, enabled: bool) {
+        self.shuffler = if enabled { Some(ControlShuffler::new(self.play_time, self.engine.get_random())) } else { None };
+    }
+
+    pub fn set_limited_vision(&mut self, enabled: bool) {
+        self.limited_vision = enabled;
+    }
+
+    pub fn set_screenshot_on_death(&mut self, enabled: bool) {
+        self.screenshot_on_death = enabled;
+    }
+
+    pub fn set_svg_export(&mut self, enabled: bool) {
+        self.export_svg = enabled;
+    }
+
+    pub fn set_endless(&mut self, enabled: bool) {
+        self.endless = enabled;
+    }
+
+    pub fn set_fresh_food(&mut self, enabled: bool) {
+        self.engine.set_fresh_food(enabled);
+    }
+
+    pub fn set_speed(&mut self, speed: u16) {
+        self.engine.set_speed(speed);
+    }
+
+    pub fn set_fixed_speed(&mut self, speed: Option<u16>) {
+        self.engine.set_fixed_speed(speed);
+    }
+
+    pub fn set_speed_curve(&mut self, curve: SpeedCurve) {
+        self.speed_curve = curve;
+    }
+
+    pub fn set_mode(&mut self, mode: GameMode) {
+        self.mode = mode;
+        self.engine.set_food_speeds_up(mode.get_ramp_interval().is_none());
+        if mode.get_ramp_interval().is_some() && !self.engine.is_fixed_speed() {
+            self.engine.set_speed(1);
+        }
+    }
+
+    pub fn set_adaptive_difficulty(&mut self, enabled: bool) {
+        self.adaptive = if enabled { Some(AdaptiveDifficulty::new()) } else { None };
+        self.engine.set_spawn_rate(1.0);
+    }
+
+    pub fn set_afk_timeout(&mut self, timeout: Option<Duration>) {
+        self.afk_timeout = timeout;
+    }
+
+    pub fn set_macro_recording(&mut self, recording: Option<InputMacro>) {
+        self.recording = recording;
+    }
+
+    pub fn set_macro_playback(&mut self, playback: Option<InputMacro>) {
+        self.playback = playback;
+    }
+
+    pub fn set_autopilot(&mut self, enabled: bool) {
+        self.autopilot = enabled;
+    }
+
+    pub fn set_high_scores(&mut self, path: Option<PathBuf>) {
+        self.high_scores = path;
+    }
+
+    pub fn add_observer(&mut self, observer: Box<dyn GameObserver>) {
+        self.observers.push(observer);
+    }
+
+    pub fn set_menu(&mut self, enabled: bool) {
+        self.menu = enabled;
+    }
+
+    pub fn set_hints(&mut self, enabled: bool) {
+        self.hints = enabled;
+    }
+
+    pub fn set_coordinates(&mut self, enabled: bool) {
+        self.coordinates = enabled;
+    }
+
+    pub fn set_head_position(&mut self, enabled: bool) {
+        self.head_position = enabled;
+    }
+
+    pub fn set_level(&mut self, level: Level) {
+        self.engine.set_obstacles(level.obstacles(self.width, self.height));
+    }
+
+    pub fn set_two_players(&mut self, enabled: bool) {
+        self.engine.set_two_players(enabled);
+        if enabled {
+            self.keymap.add_player_two();
+        }
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    pub fn set_ascii(&mut self, enabled: bool) {
+        self.theme.glyphs = if enabled { Glyphs::ASCII } else { Glyphs::UNICODE };
+    }
+
+    pub fn set_ambience(&mut self, ambience: Option<Ambience>) {
+        self.ambience = ambience.map(|ambience| AmbientLayer::new(ambience, self.width, self.height));
+    }
+
+    pub fn run(&mut self) -> Result<(), GameError> {
+        let terminal = self.prepare_ui()?;
+
+        // the game state is only described for the crash report when the game fails, not on every tick
+        let (played, high_scores) = match panic::catch_unwind(AssertUnwindSafe(|| self.show_screens())) {
+            Ok(Ok(outcome)) => outcome,
+            Ok(Err(error)) => {
+                crash::record_snapshot(self.engine.describe());
+                return Err(error);
+            }
+            Err(panic) => {
+                crash::record_snapshot(self.engine.describe());
+                panic::resume_unwind(panic);
+            }
+        };
+
+        // the summary is printed on the restored terminal
+        drop(terminal);
+        if !played {
+            return Ok(());
+        }
+
+        let score = self.engine.get_score();
+
+        let players = self.engine.get_players();
+        if players.len() > 1 {
+            let scores: Vec<String> = players.iter().map(|player| player.get_score().to_string()).collect();
+            match self.engine.get_winner() {
+                Some(winner) => println!("Player {} wins! Scores: {}", winner + 1, scores.join(" - ")),
+                None => println!("It's a draw! Scores: {}", scores.join(" - "))
+            }
+        } else if self.autopilot {
+            println!("Demo over! The autopilot scored {}", score);
+        } else if self.god {
+            println!("Game over! Your score is {} (god mode)", score);
+        } else if self.engine.is_hardcore() {
+            println!("Game over! Your hardcore score is {}", score);
+        } else if self.is_out_of_time() {
+            println!("Time's up! Your score is {}", score);
+        } else if self.practice {
+            println!("Practice over! Your score is {} (practice runs don't count)", score);
+        } else {
+            println!("Game over! Your score is {}", score); 
+        }
+
+        match high_scores {
+            Some(Ok((scores, rank))) => {
+                if let Some(rank) = rank {
+                    println!("New high score! You placed #{}", rank);
+                }
+                print!("{}", scores);
+            }
+            Some(Err(error)) => eprintln!("Failed to update high scores: {}", error),
+            None => {}
+        }
+
+        if !self.rules.is_empty() {
+            let names: Vec<&str> = self.rules.iter().map(|rule| rule.get_name()).collect();
+            println!("Endless rules reached: {}", names.join(", "));
+        }
+
+        let coins = self.engine.get_coins();
+        if coins > 0 {
+            println!("You collected {} coin{}", coins, if coins == 1 { "" } else { "s" });
+        }
+
+        for message in &self.report {
+            match message {
+                Ok(text) => println!("{}", text),
+                Err(text) => eprintln!("{}", text)
+            }
+        }
+
+        match self.save_macro() {
+            Some(Ok(name)) => println!("Macro saved to {}", name),
+            Some(Err(error)) => eprintln!("Failed to save macro: {}", error),
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    fn show_screens(&mut self) -> Result<(bool, HighScoreUpdate), GameError> {
+        // a macro only covers a single game, so there's no menu or restarting while one is recorded or replayed
+        let can_restart = self.recording.is_none() && self.playback.is_none();
+        let (columns, rows) = MENU_SIZE;
+        self.menu &= can_restart && !self.autopilot && self.width >= columns && self.height >= rows;
+
+        let mut screen = if self.menu { Screen::Menu } else { Screen::Playing };
+        let mut high_scores = None;
+        let mut played = false;
+
+        loop {
+            screen = match screen {
+                Screen::Menu => self.show_menu()?,
+                Screen::Playing => {
+                    // the first game plays on the board the game was created with, unless the menu changed its settings
+                    if played || self.menu {
+                        self.reset();
+                    }
+                    played = true;
+
+                    let ended = self.play()?;
+                    high_scores = self.update_high_scores()?;
+                    match (ended, self.menu) {
+                        (true, _) => Screen::GameOver,
+                        (false, true) => Screen::Menu,
+                        (false, false) => Screen::Quit
+                    }
+                }
+                Screen::GameOver => self.show_game_over(&high_scores, can_restart)?,
+                Screen::Quit => break
+            };
+        }
+
+        Ok((played, high_scores))
+    }
+
+    fn play(&mut self) -> Result<bool, GameError> {
+        self.render()?;
+        info!("game started on a {}x{} board, heading {:?}", self.width, self.height, self.engine.get_snake().get_direction());
+        self.last_input = Instant::now();
+        if self.autopilot {
+            self.toast = Some(Toast::new(String::from("Demo, q to quit")));
+        }
+
+        let mut done = false;
+        let mut died = false;
+        let mut last_frame = Instant::now();
+
+        while !done {
+            let tick = self.engine.get_tick();
+            let speed = self.engine.get_speed();
+
+            // a replayed macro or the autopilot doesn't need anyone at the keyboard
+            let away = self.afk_timeout.is_some_and(|timeout| self.last_input.elapsed() >= timeout);
+            if away && self.playback.is_none() && !self.autopilot {
+                info!("tick {}: paused, no input for {:?}", tick, self.last_input.elapsed());
+                self.wait_for_player("AFK? Press any key")?;
+            }
+
+            let interval = self.calculate_interval();
+            let mut now = Instant::now();
+
+            let replayed: Vec<Direction> = self.playback.iter().flat_map(|playback| playback.turns_at(tick)).collect();
+            for towards in replayed {
+                self.turn(towards);
+            }
+
+            // the autopilot decides once per tick, on the board the player would see, and queues its turn
+            // like a key press
+            if self.autopilot {
+                if let Some(towards) = autopilot::choose_turn(&self.engine) {
+                    self.queue_turn(0, towards);
+                }
+            }
+
+            while now.elapsed() < interval {
+                let wait_for = interval.saturating_sub(now.elapsed()).min(FRAME_INTERVAL.saturating_sub(last_frame.elapsed()));
+                if let Some(command) = self.get_command(wait_for) {
+                    self.last_input = Instant::now();
+                    let command = self.controls.apply(command);
+                    debug!("tick {}: {:?}", tick, command);
+
+                    match command {
+                        Command::Quit => {
+                            done = true;
+                            break;
+                        }
+                        // the time spent paused doesn't count towards the tick, or the snake would jump ahead on resume
+                        Command::Pause => now += self.wait_for_player("PAUSED")?,
+                        // and neither does the time spent waiting for the terminal to be large enough again
+                        Command::Resize(cols, rows) => match self.resize(cols, rows)? {
+                            Some(paused) => now += paused,
+                            None => {
+                                done = true;
+                                break;
+                            }
+                        },
+                        Command::Turn(towards) => if self.playback.is_none() && !self.autopilot { self.queue_turn(0, towards) },
+                        Command::TurnPlayerTwo(towards) => self.queue_turn(1, towards),
+                        Command::SpawnFood | Command::Grow | Command::Shrink => if self.god { self.engine.apply(command) },
+                        Command::Screenshot => {
+                            let text = match self.save_screenshot() {
+                                Ok(name) => format!("Saved {}", name),
+                                Err(_) => String::from("Screenshot failed")
+                            };
+                            self.toast = Some(Toast::new(text));
+                        }
+                        Command::Console => if self.god { self.open_console()? },
+                        Command::ToggleCoordinates => self.coordinates = !self.coordinates,
+                        Command::ToggleHeadPosition => self.head_position = !self.head_position,
+                        Command::SpeedUp => if self.practice || self.god { self.change_speed(true) },
+                        Command::SlowDown => if self.practice || self.god { self.change_speed(false) }
+                    }
+                }
+
+                if last_frame.elapsed() >= FRAME_INTERVAL {
+                    self.render()?;
+                    last_frame = Instant::now();
+                }
+            }
+
+            if done {
+                break;
+            }
+
+            let frame_start = Instant::now();
+
+            if let Some(controls) = self.shuffler.as_mut().and_then(|shuffler| shuffler.poll(self.play_time, self.engine.get_random())) {
+                self.controls = controls;
+            }
+
+            if let Some(towards) = self.turns[0].pop_front() {
+                self.turn(towards);
+            }
+            if let Some(towards) = self.turns[1].pop_front() {
+                self.engine.turn_player(1, towards);
+            }
+
+            let status = self.engine.step(None);
+            self.play_time += interval;
+            self.handle_events();
+
+            match status {
+                GameStatus::Over => {
+                    done = true;
+                    died = true;
+                    if self.screenshot_on_death {
+                        let message = self.save_screenshot()
+                            .map(|name| format!("Screenshot saved to {}", name))
+                            .map_err(|error| format!("Failed to save screenshot: {}", error));
+                        self.report.push(message);
+                    }
+                    self.shake_screen()?;
+                }
+                // in practice and god mode the snake just stops until it's turned away from the obstacle
+                GameStatus::Blocked => {}
+                GameStatus::Running => {
+                    self.update_effects();
+                    if let Some(adaptive) = self.adaptive.as_mut() {
+                        adaptive.tick();
+                    }
+
+                    let snake = self.engine.get_snake();
+                    trace!("tick {}: head at {:?} heading {:?}, interval {:?}", tick, snake.get_head_point(), snake.get_direction(), interval);
+
+                    self.update_rules();
+                    self.update_mode(interval);
+                    self.engine.decay_score(interval);
+                    self.engine.expire_effects(interval);
+
+                    let simulation_time = frame_start.elapsed();
+                    self.render()?;
+                    last_frame = Instant::now();
+
+                    self.profiler.record(simulation_time, frame_start.elapsed() - simulation_time, interval);
+
+                    // running out of time ends the game just like a crash, so it's followed by the game-over screen
+                    if self.is_out_of_time() {
+                        info!("tick {}: out of time", tick);
+                        done = true;
+                        died = true;
+                    }
+                }
+            }
+
+            if self.engine.get_speed() > speed {
+                let speed = self.engine.get_speed();
+                self.notify(|observer, _| observer.on_speed_up(speed));
+            }
+            self.notify(|observer, engine| observer.on_tick(engine));
+        }
+
+        self.notify(|observer, engine| observer.on_game_over(engine));
+
+        info!("game over at tick {} with score {}", self.engine.get_tick(), self.engine.get_score());
+
+        if self.export_svg {
+            let message = self.save_svg()
+                .map(|name| format!("Board exported to {}", name))
+                .map_err(|error| format!("Failed to export board: {}", error));
+            self.report.push(message);
+        }
+
+        Ok(died)
+    }
+
+    fn show_game_over(&mut self, high_scores: &HighScoreUpdate, can_restart: bool) -> Result<Screen, GameError> {
+        let players = self.engine.get_players();
+        let mut lines = if players.len() > 1 {
+            let scores: Vec<String> = players.iter().map(|player| player.get_score().to_string()).collect();
+            let title = match self.engine.get_winner() {
+                Some(winner) => format!("PLAYER {} WINS", winner + 1),
+                None => String::from("DRAW")
+            };
+            vec![title, format!("Scores {}", scores.join(" - "))]
+        } else {
+            let title = if self.autopilot {
+                "DEMO OVER"
+            } else if self.is_out_of_time() {
+                "TIME UP"
+            } else {
+                "GAME OVER"
+            };
+            vec![String::from(title), format!("Score {}", self.engine.get_score())]
+        };
+
+        if let Some(Ok((scores, rank))) = high_scores {
+            match rank {
+                Some(rank) => lines.push(format!("New high score #{}", rank)),
+                None => lines.extend(scores.get_best().map(|best| format!("Best {}", best)))
+            }
+        }
+        lines.push(format!("Speed {}", self.engine.get_speed()));
+        lines.push(String::new());
+        lines.push(String::from(match (can_restart, self.menu) {
+            (true, true) => "R restart, Esc menu",
+            (true, false) => "R restart, Esc quit",
+            _ => "Esc quit"
+        }));
+
+        let text = lines.join("\n");
+        self.dimmed = true;
+        self.render()?;
+        self.draw_overlay(&text)?;
+
+        // keys still being mashed from the game shouldn't pick an option
+        self.discard_burst();
+
+        let mut fits = true;
+        loop {
+            let key_event = match self.read_event() {
+                Ok(Event::Key(key_event)) => key_event,
+                Ok(Event::Resize(cols, rows)) => {
+                    fits = self.fit_to_terminal(cols, rows)?;
+                    if fits {
+                        self.render()?;
+                        self.draw_overlay(&text)?;
+                    }
+                    continue;
+                }
+                Ok(_) => continue,
+                Err(_) => return Ok(Screen::Quit)
+            };
+
+            let leave = if self.menu { Screen::Menu } else { Screen::Quit };
+            match key_event.code {
+                // a new game can't start until the board fits the terminal again
+                KeyCode::Char('r' | 'R') if can_restart && fits => return Ok(Screen::Playing),
+                KeyCode::Esc => return Ok(leave),
+                _ if self.keymap.command_for(key_event, Direction::Up) == Some(Command::Quit) => return Ok(leave),
+                _ => {}
+            }
+        }
+    }
+
+    fn show_menu(&mut self) -> Result<Screen, GameError> {
+        let mut menu = Menu::main();
+        self.dimmed = false;
+
+        let mut fits = true;
+        loop {
+            if fits {
+                let text = self.menu_text(&menu);
+                self.render()?;
+                self.draw_overlay(&text)?;
+            }
+
+            let key_event = match self.read_event() {
+                Ok(Event::Key(key_event)) => key_event,
+                Ok(Event::Resize(cols, rows)) => {
+                    fits = self.fit_to_terminal(cols, rows)?;
+                    continue;
+                }
+                Ok(_) => continue,
+                Err(_) => return Ok(Screen::Quit)
+            };
+
+            let item = menu.get_selected();
+            match (key_event.code, self.keymap.command_for(key_event, Direction::Up)) {
+                (KeyCode::Up, _) | (_, Some(Command::Turn(Direction::Up))) => menu.select_previous(),
+                (KeyCode::Down, _) | (_, Some(Command::Turn(Direction::Down))) => menu.select_next(),
+                (KeyCode::Left, _) | (_, Some(Command::Turn(Direction::Left))) => self.change_setting(item, false),
+                (KeyCode::Right, _) | (_, Some(Command::Turn(Direction::Right))) => self.change_setting(item, true),
+                (KeyCode::Enter | KeyCode::Char(' '), _) => match item {
+                    // a game can't start until the board fits the terminal again
+                    MenuItem::NewGame if fits => return Ok(Screen::Playing),
+                    MenuItem::Options => menu = Menu::options(),
+                    MenuItem::HighScores if fits => self.show_high_scores()?,
+                    MenuItem::Quit => return Ok(Screen::Quit),
+                    MenuItem::Back => menu = Menu::main(),
+                    _ if item.is_setting() => self.change_setting(item, true),
+                    _ => {}
+                },
+                (KeyCode::Esc, _) | (_, Some(Command::Quit)) => {
+                    if menu.is_main() {
+                        return Ok(Screen::Quit);
+                    }
+                    menu = Menu::main();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn show_high_scores(&mut self) -> Result<(), GameError> {
+        let table = std::iter::once(self.mode.get_name()).chain(self.scoring_rules()).collect::<Vec<_>>().join(" ");
+        let mut lines = vec![format!("HIGH SCORES {}", table.to_uppercase()), String::new()];
+        let path = self.high_scores.as_deref().map(|path| scores::table_path(path, self.mode, &self.scoring_rules()));
+
+        match path.map(|path| HighScores::load(&path)) {
+            None => lines.push(String::from("No high scores kept")),
+            Some(Err(_)) => lines.push(String::from("Can't read the table")),
+            Some(Ok(scores)) if scores.get_entries().is_empty() => lines.push(String::from("No high scores yet")),
+            Some(Ok(scores)) => {
+                // the table is cut off where the box would run into the bottom border
+                let room = self.height.saturating_sub(4) as usize;
+                lines.extend(scores.get_entries().iter().take(room).enumerate().map(|(i, entry)| {
+                    format!("{:>2}. {:<width$} {:>5}", i + 1, entry.get_name(), entry.get_score(), width = MAX_NAME_LENGTH)
+                }));
+            }
+        }
+
+        let text = lines.join("\n");
+        self.render()?;
+        self.draw_overlay(&text)?;
+
+        loop {
+            match self.read_event() {
+                Ok(Event::Resize(cols, rows)) => {
+                    if self.fit_to_terminal(cols, rows)? {
+                        self.render()?;
+                        self.draw_overlay(&text)?;
+                    }
+                }
+                Ok(Event::Key(_)) | Err(_) => return Ok(()),
+                Ok(_) => {}
+            }
+        }
+    }
+
+    fn menu_text(&self, menu: &Menu) -> String {
+        let labels: Vec<String> = menu.get_items().iter().map(|item| match item {
+            MenuItem::Mode => format!("Mode: {}", self.mode.get_name()),
+            MenuItem::Difficulty => format!("Difficulty: {}", Difficulty::from_curve(self.speed_curve).map_or("custom", Difficulty::get_name)),
+            MenuItem::Theme => format!("Theme: {}", self.theme.get_name().unwrap_or("custom")),
+            MenuItem::Ascii => format!("ASCII: {}", if self.theme.glyphs == Glyphs::ASCII { "on" } else { "off" }),
+            _ => String::from(item.get_label())
+        }).collect();
+
+        // the entries are padded to the same width, so they line up in the centered box
+        let width = labels.iter().map(|label| label.chars().count()).max().unwrap_or(0);
+        let mut lines = vec![String::from(menu.get_title()), String::new()];
+        for (item, label) in menu.get_items().iter().zip(labels) {
+            let marker = if *item == menu.get_selected() { '>' } else { ' ' };
+            lines.push(format!("{} {:<width$}", marker, label));
+        }
+
+        lines.join("\n")
+    }
+
+    fn change_setting(&mut self, item: MenuItem, forward: bool) {
+        match item {
+            MenuItem::Mode => self.mode = menu::cycle(&GameMode::ALL, self.mode, forward),
+            MenuItem::Difficulty => {
+                let curves = Difficulty::ALL.map(Difficulty::curve);
+                self.speed_curve = menu::cycle(&curves, self.speed_curve, forward);
+            }
+            MenuItem::Theme => {
+                // the theme keeps drawing with the characters chosen with the ASCII setting
+                let themes = Theme::ALL.map(|theme| Theme { glyphs: self.theme.glyphs, ..theme });
+                self.theme = menu::cycle(&themes, self.theme, forward);
+            }
+            MenuItem::Ascii => self.set_ascii(self.theme.glyphs != Glyphs::ASCII),
+            _ => {}
+        }
+    }
+
+    fn reset(&mut self) {
+        self.engine.reset();
+        self.play_time = Duration::ZERO;
+        self.set_speed(self.config.speed);
+        self.set_fixed_speed(self.config.fixed_speed);
+        self.set_mode(self.mode);
+
+        // the rules endless mode added only last for the game they were reached in
+        self.set_strict(self.config.strict);
+        self.set_limited_vision(self.config.limited_vision);
+        self.set_controls(self.config.controls);
+        self.set_shuffled_controls(self.config.shuffle_controls);
+        self.rules.clear();
+        if let Some(adaptive) = self.adaptive.as_mut() {
+            adaptive.restart();
+        }
+
+        self.particles = Particles::new(self.width, self.height);
+        self.trail = Trail::new();
+        self.turns = [VecDeque::new(), VecDeque::new()];
+        self.toast = None;
+        self.milestones = 0;
+        self.dimmed = false;
+        self.offset = (0, 0);
+        info!("game restarted");
+    }
+
+    fn update_mode(&mut self, elapsed: Duration) {
+        let before = self.play_time.saturating_sub(elapsed);
+
+        if let Some(ramp) = self.mode.get_ramp_interval() {
+            let levels = intervals_completed(before, self.play_time, ramp);
+            let speed = self.engine.get_speed().saturating_add(levels).min(MAX_SPEED);
+            if speed > self.engine.get_speed() && !self.engine.is_fixed_speed() {
+                self.engine.set_speed(speed);
+                self.toast = Some(Toast::new(format!("Speed {}", self.engine.get_speed())));
+            }
+        }
+
+        if let Some(reward) = self.mode.get_reward_interval() {
+            self.engine.add_points(intervals_completed(before, self.play_time, reward));
+        }
+    }
+
+    fn is_out_of_time(&self) -> bool {
+        self.mode.get_time_limit().is_some_and(|limit| self.play_time >= limit)
+    }
+
+    fn food_color(&self) -> Color {
+        let color = match (self.colors.food, self.engine.food_value()) {
+            (Some(color), _) => color,
+            (None, 1) if self.engine.is_fresh_food() => Color::DarkGrey,
+            (None, 2 | 3) => Color::Grey,
+            (None, _) => self.theme.food
+        };
+
+        match color {
+            _ if (self.frame / FOOD_PULSE_FRAMES).is_multiple_of(2) => color,
+            Color::White => Color::Grey,
+            _ => Color::DarkGrey
+        }
+    }
+
+    fn update_rules(&mut self) {
+        if !self.endless || (self.engine.get_score() / ENDLESS_RULE_SCORE) as usize <= self.rules.len() {
+            return;
+        }
+
+        let pool: Vec<Rule> = Rule::ALL.iter().copied().filter(|&rule| !self.has_rule(rule)).collect();
+        if pool.is_empty() {
+            return;
+        }
+
+        let rule = pool[self.engine.get_random().gen_range(0, pool.len() as u16) as usize];
+        match rule {
+            Rule::Strict => self.engine.set_strict(true),
+            Rule::LimitedVision => self.limited_vision = true,
+            Rule::Mirrored => self.controls = DirectionMap::mirrored(false),
+            Rule::ShuffledControls => self.set_shuffled_controls(true)
+        }
+
+        self.rules.push(rule);
+        self.toast = Some(Toast::new(format!("New rule: {}", rule.get_name())));
+    }
+
+    fn has_rule(&self, rule: Rule) -> bool {
+        match rule {
+            Rule::Strict => self.engine.is_strict(),
+            Rule::LimitedVision => self.limited_vision,
+            Rule::Mirrored => self.controls != DirectionMap::identity(),
+            Rule::ShuffledControls => self.shuffler.is_some()
+        }
+    }
+
+    fn update_effects(&mut self) {
+        if let Some(ambience) = self.ambience.as_mut() {
+            ambience.update();
+        }
+        self.particles.update();
+        self.trail.update();
+
+        if let Some(toast) = self.toast.as_mut() {
+            toast.update();
+        }
+        self.toast = self.toast.take().filter(|toast| toast.is_visible());
+    }
+
+    fn render(&mut self) -> Result<(), GameError> {
+        self.frame += 1;
+        self.buffer.clear();
+        self.draw_borders();
+        self.draw_coordinates();
+        self.draw_toast();
+        self.draw_coin_counter();
+        self.draw_scoreboard();
+        self.draw_hud();
+        self.draw_rules();
+        self.draw_effects();
+        self.draw_controls_warning();
+        self.draw_background();
+        self.draw_obstacles();
+        self.draw_trail();
+        self.draw_particles();
+        self.draw_hint();
+        self.draw_food();
+        self.draw_bug();
+        self.draw_coin();
+        self.draw_power_up();
+        self.draw_snake();
+        self.draw_profile();
+        self.draw_head_position();
+        self.present()
+    }
+
+    fn present(&mut self) -> Result<(), GameError> {
+        self.buffer.present(&mut self.stdout)?;
+        Ok(())
+    }
+
+    fn prepare_ui(&mut self) -> Result<TerminalGuard, GameError> {
+        let terminal = TerminalGuard::enter(self.width + 3, self.height + 3 + HUD_ROWS)?;
+        self.buffer.invalidate();
+        Ok(terminal)
+    }
+
+    fn calculate_interval(&self) -> Duration {
+        let interval = self.speed_curve.interval(self.engine.get_speed());
+
+        let interval = match &self.adaptive {
+            Some(adaptive) if !self.engine.is_fixed_speed() => interval.mul_f32(adaptive.get_factor()),
+            _ => interval
+        };
+
+        if self.engine.has_effect(PowerUp::SlowMotion) {
+            interval * SLOW_MOTION_FACTOR
+        } else {
+            interval
+        }
+    }
+
+    fn get_command(&mut self, wait_for: Duration) -> Option<Command> {
+        let key_event = match self.wait_for_event(wait_for)? {
+            Event::Key(key_event) => key_event,
+            Event::Resize(cols, rows) => return Some(Command::Resize(cols, rows)),
+            Event::Mouse(_) => return None
+        };
+
+        // relative turns follow on from the turns still waiting in the queue
+        let heading = self.turns[0].back().copied().unwrap_or_else(|| self.engine.get_snake().get_direction());
+        self.keymap.command_for(key_event, heading)
+    }
+
+    fn wait_for_event(&mut self, wait_for: Duration) -> Option<Event> {
+        if let Some(event) = self.pending_events.pop_front() {
+            return Some(event);
+        }
+
+        if poll(wait_for).ok()? {
+            let event = read().ok()?;
+            match event {
+                Event::Key(_) => {
+                    // a key is held back until it's clear it doesn't start a paste, in which case none of
+                    // its keys count. Players pressing at the same moment only make a key each, though
+                    let burst = self.discard_burst();
+                    if burst.len() >= self.engine.get_players().len() {
+                        return self.pending_events.pop_front();
+                    }
+
+                    for key_event in burst.into_iter().rev() {
+                        self.pending_events.push_front(Event::Key(key_event));
+                    }
+                    return Some(event);
+                }
+                Event::Resize(_, _) => return Some(event),
+                Event::Mouse(_) => {}
+            }
+        }
+
+        None
+    }
+
+    fn wait_for_player(&mut self, message: &str) -> Result<Duration, GameError> {
+        let paused_at = Instant::now();
+        self.dimmed = true;
+        self.render()?;
+        self.draw_overlay(message)?;
+
+        // a key only resumes the game once the board fits the terminal again
+        let mut fits = true;
+        loop {
+            match self.read_event() {
+                Ok(Event::Key(_)) if fits => break,
+                Ok(Event::Resize(cols, rows)) => {
+                    fits = self.fit_to_terminal(cols, rows)?;
+                    if fits {
+                        self.render()?;
+                        self.draw_overlay(message)?;
+                    }
+                }
+                Err(_) => break,
+                Ok(_) => {}
+            }
+        }
+        self.dimmed = false;
+
+        self.count_down()?;
+        self.last_input = Instant::now();
+        Ok(paused_at.elapsed())
+    }
+
+    fn count_down(&mut self) -> Result<(), GameError> {
+        for second in (1..=RESUME_COUNTDOWN).rev() {
+            self.render()?;
+            self.draw_overlay(&second.to_string())?;
+            thread::sleep(Duration::from_secs(1));
+        }
+
+        self.render()
+    }
+
+    fn resize(&mut self, cols: u16, rows: u16) -> Result<Option<Duration>, GameError> {
+        let paused_at = Instant::now();
+        let (mut cols, mut rows) = (cols, rows);
+        let mut paused = false;
+
+        while !self.fit_to_terminal(cols, rows)? {
+            if !paused {
+                info!("paused, the terminal is too small at {}x{}", cols, rows);
+                paused = true;
+            }
+
+            match self.read_event() {
+                Ok(Event::Resize(new_cols, new_rows)) => (cols, rows) = (new_cols, new_rows),
+                Ok(Event::Key(key_event)) if self.keymap.command_for(key_event, Direction::Up) == Some(Command::Quit) => return Ok(None),
+                Err(_) => return Ok(None),
+                Ok(_) => {}
+            }
+        }
+
+        if paused {
+            self.count_down()?;
+            self.last_input = Instant::now();
+        } else {
+            self.render()?;
+        }
+
+        Ok(Some(paused_at.elapsed()))
+    }
+
+    fn fit_to_terminal(&mut self, cols: u16, rows: u16) -> Result<bool, GameError> {
+        let (columns, lines) = (self.width + 3, self.height + 3 + HUD_ROWS);
+        queue!(self.stdout, Clear(ClearType::All))?;
+
+        if cols < columns || rows < lines {
+            let text = format!("Terminal too small, needs {}x{}", columns, lines);
+            let text: String = text.chars().take(cols as usize).collect();
+            queue!(self.stdout, MoveTo(0, 0), Print(text))?;
+            self.stdout.flush()?;
+            return Ok(false);
+        }
+
+        // the board is centered in whatever room the terminal has to spare
+        self.buffer.set_origin((cols - columns) / 2, (rows - lines) / 2);
+        Ok(true)
+    }
+
+    fn turn(&mut self, towards: Direction) {
+        if self.engine.turn(towards) {
+            if let Some(recording) = self.recording.as_mut() {
+                recording.record(self.engine.get_tick(), towards);
+            }
+        }
+    }
+
+    fn queue_turn(&mut self, player: usize, towards: Direction) {
+        let heading = match self.engine.get_players().get(player) {
+            Some(player) => player.get_snake().get_direction(),
+            None => return
+        };
+
+        let queue = &mut self.turns[player];
+        let last = queue.back().copied().unwrap_or(heading);
+        if towards != last && towards != last.opposite() && queue.len() < TURN_BUFFER_SIZE {
+            queue.push_back(towards);
+        }
+    }
+
+    fn handle_events(&mut self) {
+        let tick = self.engine.get_tick();
+        let events = self.engine.get_events().to_vec();
+
+        for event in events {
+            match event {
+                GameEvent::LeftCell(point) => self.trail.leave(point),
+                GameEvent::AteFood(point, kind, value) => {
+                    self.particles.spawn(point);
+                    // the adaptive difficulty measures how quickly the player gets to the food they have to go for
+                    if let Some(adaptive) = self.adaptive.as_mut().filter(|_| kind == FoodKind::Normal) {
+                        adaptive.food_eaten();
+                    }
+
+                    // the new segments are only added on the next moves, so the milestone is reached once they're all in
+                    let eater = self.engine.get_players().iter().find(|player| player.get_snake().get_head_point() == point);
+                    let length = eater.map_or(0, |player| player.get_snake().len() + player.get_snake().get_pending_growth() as usize);
+                    let growth = kind.get_growth() as usize;
+                    if growth > 0 && length / MILESTONE_LENGTH > length.saturating_sub(growth) / MILESTONE_LENGTH {
+                        self.reach_milestone(length / MILESTONE_LENGTH * MILESTONE_LENGTH);
+                    }
+
+                    self.notify(|observer, _| observer.on_food_eaten(point, kind, value));
+                    info!("tick {}: ate {:?} food at {:?} worth {}, score {}", tick, kind, point, value, self.engine.get_score());
+                }
+                GameEvent::CaughtBug(point, _) | GameEvent::CollectedCoin(point) => self.particles.spawn(point),
+                GameEvent::CollectedPowerUp(point, power_up) => {
+                    self.particles.spawn(point);
+                    self.toast = Some(Toast::new(format!("Power-up: {}", power_up.get_name())));
+                    info!("tick {}: picked up {:?} at {:?}", tick, power_up, point);
+                }
+                GameEvent::Collided(point) => {
+                    debug!("tick {}: collision at {:?} heading {:?}", tick, point, self.engine.get_snake().get_direction());
+                    if let Some(adaptive) = self.adaptive.as_mut() {
+                        adaptive.collided();
+                    }
+                }
+            }
+        }
+
+        // a struggling player gets the bug, coins and power-ups more often, and a player doing well less often
+        if let Some(adaptive) = &self.adaptive {
+            self.engine.set_spawn_rate(adaptive.get_factor());
+        }
+    }
+
+    fn change_speed(&mut self, faster: bool) {
+        let speed = self.engine.get_speed();
+        self.engine.set_speed(if faster {
+            speed.saturating_add(1).min(MAX_SPEED)
+        } else {
+            speed.saturating_sub(1).max(1)
+        });
+        self.toast = Some(Toast::new(format!("Speed {}", self.engine.get_speed())));
+    }
+
+    fn update_high_scores(&mut self) -> Result<HighScoreUpdate, GameError> {
+        if self.practice || self.god || self.playback.is_some() || self.autopilot || self.engine.get_players().len() > 1 {
+            return Ok(None);
+        }
+
+        let path = match self.high_scores.as_deref() {
+            Some(path) => scores::table_path(path, self.mode, &self.scoring_rules()),
+            None => return Ok(None)
+        };
+        let scores = match HighScores::load(&path) {
+            Ok(scores) => scores,
+            Err(error) => return Ok(Some(Err(error)))
+        };
+
+        if !scores.qualifies(self.engine.get_score()) {
+            return Ok(Some(Ok((scores, None))));
+        }
+
+        // the table is read again when the score is added, as another game may have ended in the meantime
+        match self.enter_name()? {
+            Some(name) => Ok(Some(HighScores::record(&path, &name, self.engine.get_score()))),
+            None => Ok(Some(Ok((scores, None))))
+        }
+    }
+
+    fn scoring_rules(&self) -> Vec<&'static str> {
+        let rules = [("hardcore", self.engine.is_hardcore()), ("fresh-food", self.engine.is_fresh_food()), ("bounce", self.engine.is_bounce())];
+        rules.iter().filter(|(_, enabled)| *enabled).map(|(name, _)| *name).collect()
+    }
+
+    fn enter_name(&mut self) -> Result<Option<String>, GameError> {
+        let mut name = String::new();
+        self.dimmed = true;
+        self.render()?;
+        self.draw_overlay("NEW HIGH SCORE")?;
+
+        // keys still being mashed from the game shouldn't end up in the name
+        self.discard_burst();
+
+        let mut fits = true;
+        loop {
+            if fits {
+                self.draw_name_entry(&name)?;
+            }
+
+            let key_event = match self.read_event() {
+                Ok(Event::Key(key_event)) => key_event,
+                Ok(Event::Resize(cols, rows)) => {
+                    fits = self.fit_to_terminal(cols, rows)?;
+                    if fits {
+                        self.render()?;
+                        self.draw_overlay("NEW HIGH SCORE")?;
+                    }
+                    continue;
+                }
+                Ok(_) => continue,
+                Err(_) => return Ok(None)
+            };
 
-        Self {
-            stdout,
-            original_terminal_size,
-            width,
-            height,
-            food: None, // generated when game starts
-            snake: Snake::new(
-                Point::new(width / 2, height / 2),
-                3,
-                match rand::thread_rng().gen_range(0, 4) {
-                    0 => Direction::Up,
-                    1 => Direction::Right,
-                    2 => Direction::Down,
-                    3 => Direction::Left,
-                    _ => unreachable!()
-                },
-            ),
-            speed: 20,
-            score: 0
+            match key_event.code {
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Enter if !name.trim().is_empty() => return Ok(Some(String::from(name.trim()))),
+                KeyCode::Backspace => {
+                    name.pop();
+                }
+                KeyCode::Char(c) if !c.is_control() && name.chars().count() < MAX_NAME_LENGTH => name.push(c),
+                _ => {}
+            }
         }
     }
 
-    pub fn run(&mut self) {
-        self.place_food();
-        self.prepare_ui();
-        self.render();
+    fn open_console(&mut self) -> Result<(), GameError> {
+        let mut line = String::new();
 
-        let mut done = false;
+        loop {
+            self.draw_console(&line)?;
 
-        while !done {
-            let interval = self.calculate_interval();
-            let direction = self.snake.get_direction();
-            let now = Instant::now();
+            let key_event = match self.read_event() {
+                Ok(Event::Key(key_event)) => key_event,
+                Ok(_) => continue,
+                Err(_) => break
+            };
 
-            while now.elapsed() < interval {
-                if let Some(command) = self.get_command(interval - now.elapsed()) {
-                    match command {
-                        Command::Quit => {
-                            done = true;
-                            break;
-                        }
-                        Command::Turn(towards) => {
-                            if direction != towards && direction.opposite() != towards {
-                                self.snake.set_direction(towards); 
-                            }
-                        }
-                    }
+            match key_event.code {
+                KeyCode::Esc | KeyCode::Char('`') => break,
+                KeyCode::Enter => {
+                    let text = match ConsoleCommand::parse(&line).and_then(|command| self.run_console_command(command)) {
+                        Ok(text) | Err(text) => text
+                    };
+                    self.toast = Some(Toast::new(text));
+                    break;
                 }
+                KeyCode::Backspace => {
+                    line.pop();
+                }
+                KeyCode::Char(c) => line.push(c),
+                _ => {}
             }
+        }
 
-            if self.has_collidated_with_wall() || self.has_bitten_itself() {
-                done = true;
-            } else {
-                self.snake.slither();
-
-                if let Some(food_point) = self.food {
-                    if self.snake.get_head_point() == food_point {
-                        self.snake.grow(); 
-                        self.place_food();
-                        self.score += 1;
+        self.render()
+    }
 
-                        if self.score % ((self.width  * self.height) / MAX_SPEED) == 0 {
-                            self.speed += 1
-                        }
-                    }
+    fn run_console_command(&mut self, command: ConsoleCommand) -> Result<String, String> {
+        match command {
+            ConsoleCommand::SpawnFood(point) => {
+                if point.x >= self.width || point.y >= self.height || self.engine.is_occupied(&point) {
+                    return Err(String::from("Cell isn't free"));
+                }
+                self.engine.set_food(point);
+                Ok(format!("Food at {} {}", point.x, point.y))
+            }
+            ConsoleCommand::SetSpeed(speed) => {
+                if !(1..=MAX_SPEED).contains(&speed) {
+                    return Err(format!("Speed must be 1-{}", MAX_SPEED));
+                }
+                self.engine.set_speed(speed);
+                Ok(format!("Speed {}", speed))
+            }
+            ConsoleCommand::Teleport(head) => {
+                if !self.engine.teleport(head) {
+                    return Err(String::from("Snake doesn't fit"));
                 }
+                Ok(format!("Teleported to {} {}", head.x, head.y))
+            }
+            ConsoleCommand::Grow(segments) => {
+                self.engine.grow_snake(segments);
+                Ok(format!("Growing {} segments", segments))
+            }
+            ConsoleCommand::Seed(seed) => {
+                self.engine.set_random(Box::new(SeededRandom::new(seed)));
+                Ok(format!("Seed {}", seed))
+            }
+        }
+    }
 
-                self.render();
+    fn discard_burst(&mut self) -> Vec<KeyEvent> {
+        let mut keys = Vec::new();
+        while poll(PASTE_GAP).unwrap_or(false) {
+            match read() {
+                Ok(Event::Key(key_event)) => keys.push(key_event),
+                Ok(event) => self.pending_events.push_back(event),
+                Err(_) => break
             }
+        }
+
+        keys
+    }
 
+    fn read_event(&mut self) -> crossterm::Result<Event> {
+        match self.pending_events.pop_front() {
+            Some(event) => Ok(event),
+            None => read()
         }
+    }
 
-        self.restore_ui();
+    fn notify(&mut self, callback: impl Fn(&mut dyn GameObserver, &GameEngine)) {
+        for observer in self.observers.iter_mut() {
+            callback(observer.as_mut(), &self.engine);
+        }
+    }
 
-        println!("Game over! Your score is {}", self.score); 
+    fn reach_milestone(&mut self, length: usize) {
+        self.milestones += 1;
+        self.toast = Some(Toast::new(format!("Length {}!", length)));
     }
 
-    fn place_food(&mut self) {
-        loop {
-            let random_x = rand::thread_rng().gen_range(0, self.width);
-            let random_y = rand::thread_rng().gen_range(0, self.height);
+    fn save_screenshot(&self) -> image::ImageResult<String> {
+        let mut screenshot = Screenshot::new(self.width, self.height, self.border_color());
 
-            let point = Point::new(random_x, random_y);
-            if !self.snake.contains_point(&point) {
-                self.food = Some(point);
-                break;
+        for food in self.engine.get_foods() {
+            let color = match food.get_kind() {
+                FoodKind::Normal => self.colors.food.unwrap_or(self.theme.food),
+                FoodKind::Bonus => self.theme.bonus_food,
+                FoodKind::Shrink => self.theme.shrink_food
+            };
+            screenshot.paint(&food.get_point(), color);
+        }
+        if let Some(bug) = self.engine.get_bug() {
+            for point in bug.get_points() {
+                screenshot.paint(point, Color::Magenta);
+            }
+        }
+        for coin in self.engine.get_coin().iter() {
+            screenshot.paint(coin, Color::Yellow);
+        }
+        for obstacle in self.engine.get_obstacles() {
+            screenshot.paint(obstacle, self.border_color());
+        }
+        if let Some((point, _)) = self.engine.get_power_up() {
+            screenshot.paint(&point, POWER_UP_COLOR);
+        }
+        for (i, player) in self.engine.get_players().iter().enumerate() {
+            for body in player.get_snake().get_body_points().iter() {
+                screenshot.paint(body, self.player_color(i));
             }
         }
-    }
 
-    fn render(&mut self) {
-        self.draw_borders();
-        self.draw_background();
-        self.draw_food();
-        self.draw_snake();
-    }
+        let name = Self::export_file_name("png");
+        screenshot.save(Path::new(&name))?;
 
-    fn prepare_ui(&mut self) {
-        enable_raw_mode().unwrap();
-        self.stdout
-            .execute(SetSize(self.width + 3, self.height + 3)).unwrap()
-            .execute(Clear(ClearType::All)).unwrap()
-            .execute(Hide).unwrap();
+        Ok(name)
     }
 
-    fn calculate_interval(&self) -> Duration {
-        let speed = MAX_SPEED - self.speed;
-        Duration::from_millis(
-            (MIN_INTERVAL + (((MAX_INTERVAL - MIN_INTERVAL) / MAX_SPEED) * speed)) as u64
-        )
+    fn save_macro(&self) -> Option<io::Result<String>> {
+        let recording = self.recording.as_ref()?;
+        let name = Self::export_file_name("macro");
+
+        Some(recording.save(Path::new(&name)).map(|_| name))
     }
 
-    fn get_command(&self, wait_for: Duration) -> Option<Command> {
-        let key_event = self.wait_for_key_event(wait_for)?;
+    fn save_svg(&self) -> io::Result<String> {
+        let mut svg = SvgSnapshot::new(self.width, self.height, self.border_color());
 
-        match key_event.code {
-            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => Some(Command::Quit),
-            KeyCode::Char('c') | KeyCode::Char('C') =>
-                if key_event.modifiers == KeyModifiers::CONTROL {
-                    Some(Command::Quit)
-                } else {
-                    None
-                }
-            KeyCode::Up => Some(Command::Turn(Direction::Up)),
-            KeyCode::Right => Some(Command::Turn(Direction::Right)),
-            KeyCode::Down => Some(Command::Turn(Direction::Down)),
-            KeyCode::Left => Some(Command::Turn(Direction::Left)),
-            _ => None
+        for food in self.engine.get_foods() {
+            let color = match food.get_kind() {
+                FoodKind::Normal => self.colors.food.unwrap_or(self.theme.food),
+                FoodKind::Bonus => self.theme.bonus_food,
+                FoodKind::Shrink => self.theme.shrink_food
+            };
+            svg.add_dot(&food.get_point(), color);
         }
+        if let Some(bug) = self.engine.get_bug() {
+            for point in bug.get_points() {
+                svg.add_cell(point, Color::Magenta);
+            }
+        }
+        for coin in self.engine.get_coin().iter() {
+            svg.add_dot(coin, Color::Yellow);
+        }
+        for obstacle in self.engine.get_obstacles() {
+            svg.add_cell(obstacle, self.border_color());
+        }
+        if let Some((point, _)) = self.engine.get_power_up() {
+            svg.add_dot(&point, POWER_UP_COLOR);
+        }
+        for (i, player) in self.engine.get_players().iter().enumerate() {
+            svg.add_path(player.get_snake().get_body_points(), self.player_color(i));
+        }
+
+        let name = Self::export_file_name("svg");
+        svg.save(Path::new(&name))?;
+
+        Ok(name)
     }
 
-    fn wait_for_key_event(&self, wait_for: Duration) -> Option<KeyEvent> {
-        if poll(wait_for).ok()? {
-            let event = read().ok()?;
-            if let Event::Key(key_event) = event {
-                return Some(key_event);
-            }
+    fn export_file_name(extension: &str) -> String {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+        let mut name = format!("snake-{}.{}", timestamp, extension);
+
+        // two exports within the same millisecond get a counter rather than overwriting each other
+        let mut count = 1;
+        while Path::new(&name).exists() {
+            count += 1;
+            name = format!("snake-{}-{}.{}", timestamp, count, extension);
         }
 
-        None
+        name
     }
 
-    fn has_collidated_with_wall(&self) -> bool {
-        let head_point = self.snake.get_head_point();
+    fn shake_screen(&mut self) -> Result<(), GameError> {
+        for offset in ScreenShake::new() {
+            self.offset = offset;
+            self.render()?;
+            thread::sleep(Duration::from_millis(SHAKE_FRAME_INTERVAL));
+        }
+
+        Ok(())
+    }
 
-        match self.snake.get_direction() {
-            Direction::Up => head_point.y == 0,
-            Direction::Right => head_point.x == self.width - 1,
-            Direction::Down => head_point.y == self.height - 1,
-            Direction::Left => head_point.x == 0,
+    fn snake_color(&self) -> Color {
+        if self.engine.has_effect(PowerUp::Ghost) {
+            return GHOST_COLOR;
         }
+        if let Some(color) = self.colors.snake {
+            return color;
+        }
+
+        self.theme.snake[self.engine.get_speed() as usize % self.theme.snake.len()]
     }
 
-    fn has_bitten_itself(&self) -> bool {
-        let next_head_point = self.snake.get_head_point().transform(self.snake.get_direction(), 1);
-        let mut next_body_points = self.snake.get_body_points().clone();
+    fn player_color(&self, player: usize) -> Color {
+        if player == 0 { self.snake_color() } else { self.theme.player_two }
+    }
 
-        next_body_points.remove(next_body_points.len() - 1);
-        next_body_points.remove(0);
+    fn border_color(&self) -> Color {
+        if let Some(color) = self.colors.border {
+            color
+        } else if self.milestone_borders {
+            MILESTONE_BORDER_COLORS[self.milestones % MILESTONE_BORDER_COLORS.len()]
+        } else {
+            self.theme.border
+        }
+    }
 
-        next_body_points.contains(&next_head_point)
+    fn shade(&self, color: Color) -> Color {
+        if self.dimmed { Color::DarkGrey } else { color }
     }
 
-    fn restore_ui(&mut self) {
-        let (cols, rows) = self.original_terminal_size;
-        self.stdout
-            .execute(SetSize(cols, rows)).unwrap()
-            .execute(Clear(ClearType::All)).unwrap()
-            .execute(Show).unwrap()
-            .execute(ResetColor).unwrap();
-            disable_raw_mode().unwrap();
+    fn themed(&self, symbol: char) -> char {
+        if symbol == Glyphs::UNICODE.dot { self.theme.glyphs.dot } else { symbol }
     }
 
     fn draw_snake(&mut self) {
-        let fg = SetForegroundColor(match self.speed % 3 {
-            0 => Color::Green,
-            1 => Color::Cyan,
-            _ => Color::Yellow
-        });
-        self.stdout.execute(fg).unwrap();
-
-        let body_points = self.snake.get_body_points();
-        for (i, body) in body_points.iter().enumerate() {
-            let previous = if i == 0 { None } else { body_points.get(i - 1) };
-            let next = body_points.get(i + 1);
-            let symbol = if let Some(&next) = next {
-                if let Some(&previous) = previous {
-                    if previous.x == next.x {
-                        '║'
-                    } else if previous.y == next.y {
-                        '═'
-                    } else {
-                        let d = body.transform(Direction::Down, 1);
-                        let r = body.transform(Direction::Right, 1);
-                        let u = if body.y == 0 { body.clone() } else { body.transform(Direction::Up, 1) };
-                        let l = if body.x == 0 { body.clone() } else { body.transform(Direction::Left, 1) };
-                        if (next == d && previous == r) || (previous == d && next == r) {
-                            '╔'
-                        } else if (next == d && previous == l) || (previous == d && next == l) {
-                            '╗'
-                        } else if (next == u && previous == r) || (previous == u && next == r) {
-                            '╚'
+        let (dx, dy) = self.offset;
+        let glyphs = self.theme.glyphs;
+
+        for (player, snake) in self.engine.get_players().iter().map(|player| player.get_snake()).enumerate() {
+            let color = self.shade(self.player_color(player));
+
+            let body_points = snake.get_body_points();
+            for (i, body) in body_points.iter().enumerate() {
+                if !self.is_visible(body) {
+                    continue;
+                }
+
+                let previous = if i == 0 { None } else { body_points.get(i - 1) };
+                let next = body_points.get(i + 1);
+                let symbol = if let Some(&next) = next {
+                    if let Some(&previous) = previous {
+                        if previous.x == next.x {
+                            glyphs.vertical
+                        } else if previous.y == next.y {
+                            glyphs.horizontal
                         } else {
-                            '╝'
+                            let d = body.transform(Direction::Down, 1);
+                            let r = body.transform(Direction::Right, 1);
+                            let u = if body.y == 0 { body.clone() } else { body.transform(Direction::Up, 1) };
+                            let l = if body.x == 0 { body.clone() } else { body.transform(Direction::Left, 1) };
+                            if (next == d && previous == r) || (previous == d && next == r) {
+                                glyphs.top_left
+                            } else if (next == d && previous == l) || (previous == d && next == l) {
+                                glyphs.top_right
+                            } else if (next == u && previous == r) || (previous == u && next == r) {
+                                glyphs.bottom_left
+                            } else {
+                                glyphs.bottom_right
+                            }
                         }
+                    } else {
+                        glyphs.head
+                    }
+                } else if let Some(&previous) = previous {
+                    if body.y == previous.y {
+                        glyphs.horizontal
+                    } else {
+                        glyphs.vertical
                     }
                 } else {
-                    'O'
-                }
-            } else if let Some(&previous) = previous {
-                if body.y == previous.y {
-                    '═'
-                } else {
-                    '║'
-                }
-            } else {
-                panic!("Invalid snake body point.");
-            };
+                    panic!("Invalid snake body point.");
+                };
 
-            self.stdout
-                .execute(MoveTo(body.x + 1, body.y + 1)).unwrap()
-                .execute(Print(symbol)).unwrap();
+                self.buffer.put(body.x + 1 + dx, body.y + 1 + dy, symbol, color);
+            }
         }
     }
 
     fn draw_food(&mut self) {
-        self.stdout.execute(SetForegroundColor(Color::White)).unwrap();
+        let (dx, dy) = self.offset;
+
+        for food in self.engine.get_foods() {
+            let point = food.get_point();
+            if !self.is_visible(&point) {
+                continue;
+            }
+
+            let (glyph, color) = match food.get_kind() {
+                FoodKind::Normal => (self.theme.glyphs.food, self.food_color()),
+                FoodKind::Bonus => (self.theme.glyphs.bonus_food, self.theme.bonus_food),
+                FoodKind::Shrink => (self.theme.glyphs.shrink_food, self.theme.shrink_food)
+            };
+            let color = self.shade(color);
+            self.buffer.put(point.x + 1 + dx, point.y + 1 + dy, glyph, color);
+        }
+    }
+
+    fn draw_bug(&mut self) {
+        let (dx, dy) = self.offset;
+        let color = self.shade(Color::Magenta);
+        let glyphs = self.theme.glyphs.bug;
+        let glyph = glyphs[self.frame as usize % glyphs.len()];
+
+        if let Some(bug) = self.engine.get_bug() {
+            for point in bug.get_points() {
+                if !self.is_visible(point) {
+                    continue;
+                }
+
+                self.buffer.put(point.x + 1 + dx, point.y + 1 + dy, glyph, color);
+            }
+        }
+    }
+
+    fn draw_coin(&mut self) {
+        let (dx, dy) = self.offset;
+        let color = self.shade(Color::Yellow);
+        let glyph = COIN_GLYPHS[self.frame as usize % COIN_GLYPHS.len()];
+
+        for coin in self.engine.get_coin().iter() {
+            if !self.is_visible(coin) {
+                continue;
+            }
+
+            self.buffer.put(coin.x + 1 + dx, coin.y + 1 + dy, glyph, color);
+        }
+    }
 
-        for food in self.food.iter() {
-            self.stdout
-                .execute(MoveTo(food.x + 1, food.y + 1)).unwrap()
-                .execute(Print("•")).unwrap();
+    fn draw_power_up(&mut self) {
+        let (dx, dy) = self.offset;
+
+        if let Some((point, power_up)) = self.engine.get_power_up() {
+            if !self.is_visible(&point) {
+                return;
+            }
+
+            let glyph = match power_up {
+                PowerUp::SlowMotion => '~',
+                PowerUp::Ghost => '@',
+                PowerUp::DoublePoints => '2'
+            };
+            let color = self.shade(POWER_UP_COLOR);
+            self.buffer.put(point.x + 1 + dx, point.y + 1 + dy, glyph, color);
         }
     }
 
     fn draw_background(&mut self) {
-        self.stdout.execute(ResetColor).unwrap();
+        let (dx, dy) = self.offset;
 
         for y in 1..self.height + 1 {
             for x in 1..self.width + 1 {
-                self.stdout
-                    .execute(MoveTo(x, y)).unwrap()
-                    .execute(Print(" ")).unwrap();
+                let point = Point::new(x - 1, y - 1);
+                let visible = self.is_visible(&point);
+                if visible && self.engine.is_occupied(&point) {
+                    continue;
+                }
+
+                let symbol = self.ambience.as_ref()
+                    .filter(|_| visible)
+                    .and_then(|ambience| ambience.glyph_at(&point))
+                    .map(|symbol| self.themed(symbol))
+                    .unwrap_or(' ');
+
+                self.buffer.put(x + dx, y + dy, symbol, Color::DarkGrey);
+            }
+        }
+    }
+
+    fn draw_obstacles(&mut self) {
+        let (dx, dy) = self.offset;
+        let color = self.border_color();
+
+        for obstacle in self.engine.get_obstacles() {
+            if self.is_visible(obstacle) {
+                self.buffer.put(obstacle.x + 1 + dx, obstacle.y + 1 + dy, self.theme.glyphs.border, color);
+            }
+        }
+    }
+
+    fn draw_trail(&mut self) {
+        let (dx, dy) = self.offset;
+
+        for (point, glyph, color) in self.trail.cells() {
+            if self.is_visible(&point) && !self.engine.is_occupied(&point) {
+                let color = self.shade(color);
+                let glyph = self.themed(glyph);
+                self.buffer.put(point.x + 1 + dx, point.y + 1 + dy, glyph, color);
+            }
+        }
+    }
+
+    fn draw_hint(&mut self) {
+        if !self.hints || !(self.practice || self.god) {
+            return;
+        }
+
+        let (dx, dy) = self.offset;
+        let food = match self.engine.get_food() {
+            Some(food) => food,
+            None => return
+        };
+
+        // the tail moves out of the way, so only the rest of the body blocks the path
+        let players = self.engine.get_players();
+        let head = self.engine.get_snake().get_head_point();
+        let path = pathfinding::shortest_path(self.width, self.height, head, food, self.engine.is_wrap_around(), |point| {
+            self.engine.is_obstacle(point) || players.iter().map(|player| player.get_snake())
+                .any(|snake| *point != snake.get_tail_point() && snake.contains_point(point))
+        });
+
+        let color = self.shade(Color::DarkCyan);
+        for point in path.unwrap_or_default() {
+            if self.is_visible(&point) && !self.engine.is_occupied(&point) {
+                self.buffer.put(point.x + 1 + dx, point.y + 1 + dy, self.theme.glyphs.dot, color);
+            }
+        }
+    }
+
+    fn draw_particles(&mut self) {
+        let (dx, dy) = self.offset;
+        let color = self.shade(Color::Yellow);
+
+        for (point, glyph) in self.particles.cells() {
+            if self.is_visible(&point) && !self.engine.is_occupied(&point) {
+                let glyph = self.themed(glyph);
+                self.buffer.put(point.x + 1 + dx, point.y + 1 + dy, glyph, color);
             }
         }
     }
 
+    fn is_visible(&self, point: &Point) -> bool {
+        if !self.limited_vision {
+            return true;
+        }
+
+        let head_point = self.engine.get_snake().get_head_point();
+        let dx = point.x as i32 - head_point.x as i32;
+        let dy = point.y as i32 - head_point.y as i32;
+
+        // distance in front of the head, and distance to the side of the line the snake is moving along
+        let (ahead, aside) = match self.engine.get_snake().get_direction() {
+            Direction::Up => (-dy, dx),
+            Direction::Right => (dx, dy),
+            Direction::Down => (dy, dx),
+            Direction::Left => (-dx, dy)
+        };
+
+        (aside == 0 && ahead > 0 && ahead <= VISION_RANGE)
+            || (dx.abs() <= VISION_RADIUS && dy.abs() <= VISION_RADIUS)
+    }
+
     fn draw_borders(&mut self) {
-        self.stdout.execute(SetForegroundColor(Color::DarkGrey)).unwrap();
+        let (dx, dy) = self.offset;
+        let color = self.border_color();
+        let glyph = self.theme.glyphs.border;
 
         for y in 0..self.height + 2 {
-            self.stdout
-                .execute(MoveTo(0, y)).unwrap()
-                .execute(Print("#")).unwrap()
-                .execute(MoveTo(self.width + 1, y)).unwrap()
-                .execute(Print("#")).unwrap();
+            self.buffer.put(dx, y + dy, glyph, color);
+            self.buffer.put(self.width + 1 + dx, y + dy, glyph, color);
         }
 
         for x in 0..self.width + 2 {
-            self.stdout
-                .execute(MoveTo(x, 0)).unwrap()
-                .execute(Print("#")).unwrap()
-                .execute(MoveTo(x, self.height + 1)).unwrap()
-                .execute(Print("#")).unwrap();
-        }
-
-        self.stdout
-            .execute(MoveTo(0, 0)).unwrap()
-            .execute(Print("#")).unwrap()
-            .execute(MoveTo(self.width + 1, self.height + 1)).unwrap()
-            .execute(Print("#")).unwrap()
-            .execute(MoveTo(self.width + 1, 0)).unwrap()
-            .execute(Print("#")).unwrap()
-            .execute(MoveTo(0, self.height + 1)).unwrap()
-            .execute(Print("#")).unwrap();
+            self.buffer.put(x + dx, dy, glyph, color);
+            self.buffer.put(x + dx, self.height + 1 + dy, glyph, color);
+        }
+    }
+
+    fn draw_controls_warning(&mut self) {
+        let (dx, dy) = self.offset;
+
+        if let Some((controls, left)) = self.shuffler.as_ref().and_then(|shuffler| shuffler.get_warning(self.play_time)) {
+            let text = format!(" {}s {} ", left.as_secs() + 1, controls.describe(self.theme.glyphs.arrows));
+            let length = text.chars().count() as u16;
+
+            if length <= self.width {
+                self.buffer.print((self.width + 2 - length) / 2 + dx, self.height + 1 + dy, &text, Color::Red);
+            }
+        }
+    }
+
+    fn draw_coin_counter(&mut self) {
+        let (dx, dy) = self.offset;
+        let text = format!(" ${} ", self.engine.get_coins());
+
+        if text.chars().count() as u16 <= self.width {
+            self.buffer.print(2 + dx, self.height + 1 + dy, &text, Color::Yellow);
+        }
+    }
+
+    fn draw_scoreboard(&mut self) {
+        let players = self.engine.get_players();
+        if players.len() < 2 {
+            return;
+        }
+
+        let (dx, dy) = self.offset;
+        let scores: Vec<String> = players.iter().map(|player| format!(" {} ", player.get_score())).collect();
+        let length = scores.iter().map(|score| score.chars().count() as u16).sum::<u16>();
+        if length > self.width {
+            return;
+        }
+
+        let mut x = self.width - length + dx;
+        for (player, score) in scores.iter().enumerate() {
+            self.buffer.print(x, dy, score, self.player_color(player));
+            x += score.chars().count() as u16;
+        }
+    }
+
+    fn draw_hud(&mut self) {
+        let (dx, dy) = self.offset;
+        let players = self.engine.get_players();
+        let scores: Vec<String> = players.iter().map(|player| player.get_score().to_string()).collect();
+        let lengths: Vec<String> = players.iter().map(|player| player.get_snake().len().to_string()).collect();
+        let seconds = match self.mode.get_time_limit() {
+            Some(limit) => limit.saturating_sub(self.play_time).as_secs(),
+            None => self.play_time.as_secs()
+        };
+
+        let time = format!("{}:{:02}", seconds / 60, seconds % 60);
+        let text = format!("Score {}  Speed {}  Length {}", scores.join("-"), self.engine.get_speed(), lengths.join("-"));
+
+        // the timer sits at the right edge of the board, and on a narrow board the rest is cut off before it
+        let columns = self.width as usize + 2;
+        let room = columns.saturating_sub(time.chars().count() + 1);
+        let text: String = text.chars().take(room).collect();
+
+        let color = self.shade(Color::White);
+        self.buffer.print(dx, self.height + 2 + dy, &text, color);
+        if time.chars().count() <= columns {
+            self.buffer.print((columns - time.chars().count()) as u16 + dx, self.height + 2 + dy, &time, color);
+        }
+    }
+
+    fn draw_rules(&mut self) {
+        if self.rules.is_empty() {
+            return;
+        }
+
+        let (dx, dy) = self.offset;
+        let names: Vec<&str> = self.rules.iter().map(|rule| rule.get_name()).collect();
+        let text = format!(" {} ", names.join(" "));
+        let length = text.chars().count() as u16;
+
+        if length <= self.width {
+            self.buffer.print(self.width - length + dx, self.height + 1 + dy, &text, Color::DarkYellow);
+        }
+    }
+
+    fn draw_effects(&mut self) {
+        let effects = self.engine.get_effects();
+        if effects.is_empty() {
+            return;
+        }
+
+        let (dx, dy) = self.offset;
+        let names: Vec<String> = effects.iter()
+            .map(|effect| format!("{} {}s", effect.get_power_up().get_name(), effect.get_remaining().as_secs() + 1))
+            .collect();
+        let text = format!(" {} ", names.join(" "));
+
+        if text.chars().count() as u16 <= self.width {
+            self.buffer.print(2 + dx, dy, &text, POWER_UP_COLOR);
+        }
+    }
+
+    fn draw_coordinates(&mut self) {
+        if !self.coordinates {
+            return;
+        }
+
+        let (dx, dy) = self.offset;
+
+        for x in 0..self.width {
+            self.buffer.print(x + 1 + dx, dy, &(x % 10).to_string(), Color::DarkGrey);
+        }
+
+        for y in 0..self.height {
+            self.buffer.print(dx, y + 1 + dy, &(y % 10).to_string(), Color::DarkGrey);
+        }
+    }
+
+    fn draw_profile(&mut self) {
+        if !self.coordinates {
+            return;
+        }
+
+        let (dx, dy) = self.offset;
+        let lines = self.profiler.summary();
+
+        // a line each, right below the top border, as far as the board is high
+        for (row, line) in lines.iter().take(self.height as usize).enumerate() {
+            let text = format!(" {}", line);
+            let length = text.chars().count() as u16;
+            if length <= self.width {
+                self.buffer.print(self.width + 1 - length + dx, row as u16 + 1 + dy, &text, Color::DarkGrey);
+            }
+        }
+    }
+
+    fn draw_head_position(&mut self) {
+        if !self.head_position {
+            return;
+        }
+
+        let (dx, dy) = self.offset;
+        let head_point = self.engine.get_snake().get_head_point();
+        let text = format!("{},{} ", head_point.x, head_point.y);
+
+        if text.chars().count() as u16 <= self.width {
+            self.buffer.print(1 + dx, self.height + dy, &text, Color::White);
+        }
+    }
+
+    fn draw_overlay(&mut self, text: &str) -> Result<(), GameError> {
+        let (dx, dy) = self.offset;
+        let lines: Vec<&str> = text.lines().collect();
+        let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+        let length = width as u16 + 4;
+        let rows = lines.len() as u16;
+
+        // the box is dropped rather than drawn over the borders of a small board
+        if length > self.width || self.height < rows + 2 {
+            return Ok(());
+        }
+
+        let x = (self.width + 2 - length) / 2 + dx;
+        let y = self.height / 2 + 1 - rows / 2 + dy;
+        let glyphs = self.theme.glyphs;
+        let border: String = std::iter::repeat_n(glyphs.horizontal, width + 2).collect();
+
+        self.buffer.print(x, y - 1, &format!("{}{}{}", glyphs.top_left, border, glyphs.top_right), Color::White);
+        for (row, line) in lines.iter().enumerate() {
+            let text = format!("{} {:^width$} {}", glyphs.vertical, line, glyphs.vertical);
+            self.buffer.print(x, y + row as u16, &text, Color::White);
+        }
+        self.buffer.print(x, y + rows, &format!("{}{}{}", glyphs.bottom_left, border, glyphs.bottom_right), Color::White);
+        self.present()
+    }
+
+    fn draw_name_entry(&mut self, name: &str) -> Result<(), GameError> {
+        let (dx, dy) = self.offset;
+        self.draw_borders();
+
+        // on a narrow board, only the end of the prompt is shown
+        let text: Vec<char> = format!(" Name: {}_ ", name).chars().collect();
+        let visible = text.len().saturating_sub(self.width as usize);
+        let text: String = text[visible..].iter().collect();
+
+        self.buffer.print(1 + dx, self.height + 1 + dy, &text, Color::White);
+        self.present()
+    }
+
+    fn draw_console(&mut self, line: &str) -> Result<(), GameError> {
+        let (dx, dy) = self.offset;
+        self.draw_borders();
+
+        // when the line gets too long, only its end is shown
+        let text: Vec<char> = format!(" > {}_ ", line).chars().collect();
+        let visible = text.len().saturating_sub(self.width as usize);
+        let text: String = text[visible..].iter().collect();
+
+        self.buffer.print(1 + dx, self.height + 1 + dy, &text, Color::White);
+        self.present()
+    }
+
+    fn draw_toast(&mut self) {
+        let (dx, dy) = self.offset;
+
+        if let Some(toast) = &self.toast {
+            let text = format!(" {} ", toast.get_text());
+            let length = text.chars().count() as u16;
+
+            // the toast is dropped rather than drawn over the corners of a narrow board
+            if length <= self.width {
+                self.buffer.print((self.width + 2 - length) / 2 + dx, dy, &text, Color::White);
+            }
+        }
+    }
+}
+
+// counts how many whole intervals were completed between two points in the play time
+fn intervals_completed(from: Duration, to: Duration, interval: Duration) -> u16 {
+    (to.as_millis() / interval.as_millis() - from.as_millis() / interval.as_millis()) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn survival_speeds_up_with_every_ramp_step_until_max_speed() {
+        let mut game = Game::new(Vec::new(), &Config::default());
+        game.set_adaptive_difficulty(true);
+        game.set_mode(GameMode::Survival);
+        let ramp = GameMode::Survival.get_ramp_interval().unwrap();
+        assert_eq!(game.engine.get_speed(), 1);
+
+        for speed in 2..=MAX_SPEED {
+            let interval = game.calculate_interval();
+            game.play_time += ramp;
+            game.update_mode(ramp);
+
+            assert_eq!(game.engine.get_speed(), speed);
+            assert!(game.calculate_interval() < interval || interval == game.speed_curve.get_floor());
+        }
+
+        game.play_time += ramp;
+        game.update_mode(ramp);
+        assert_eq!(game.engine.get_speed(), MAX_SPEED);
+        assert!(!game.engine.is_fixed_speed());
+    }
+
+    #[test]
+    fn adaptive_difficulty_carries_over_to_the_next_game() {
+        let mut game = Game::new(Vec::new(), &Config { adaptive: true, ..Config::default() });
+        game.adaptive.as_mut().unwrap().collided();
+        let factor = game.adaptive.as_ref().unwrap().get_factor();
+        assert!(factor > 1.0);
+
+        game.reset();
+        assert_eq!(game.adaptive.as_ref().map(AdaptiveDifficulty::get_factor), Some(factor));
+    }
+
+    #[test]
+    fn survival_keeps_a_fixed_speed() {
+        let mut game = Game::new(Vec::new(), &Config::default());
+        game.set_fixed_speed(Some(5));
+        game.set_mode(GameMode::Survival);
+        let ramp = GameMode::Survival.get_ramp_interval().unwrap();
+
+        game.play_time += ramp;
+        game.update_mode(ramp);
+        assert_eq!(game.engine.get_speed(), 5);
+    }
+
+    #[test]
+    fn classic_after_survival_speeds_up_with_the_food_again() {
+        let mut game = Game::new(Vec::new(), &Config::default());
+        game.set_mode(GameMode::Survival);
+        game.set_mode(GameMode::Classic);
+
+        assert!(!game.engine.is_fixed_speed());
     }
 }
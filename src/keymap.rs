@@ -0,0 +1,425 @@
+use crate::command::Command;
+use crate::controls::{ControlScheme, Layout};
+use crate::direction::Direction;
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents something a key can be bound to.
+///
+/// Most actions issue the `Command` of the same name. The direction actions turn the first snake, the
+/// relative ones turn it left or right of its current heading and the `PlayerTwo` ones turn the
+/// second snake in a two player game.
+///
+/// # Variants
+/// - `Quit`, `Pause`: Quit or pause the game.
+/// - `Up`, `Right`, `Down`, `Left`: Turn the first snake in a direction.
+/// - `TurnLeft`, `TurnRight`: Turn the first snake left or right of its heading.
+/// - `PlayerTwoUp`, `PlayerTwoRight`, `PlayerTwoDown`, `PlayerTwoLeft`: Turn the second snake in a direction.
+/// - `SpawnFood`, `Grow`, `Shrink`, `Console`: The god mode commands.
+/// - `Screenshot`, `ToggleCoordinates`, `ToggleHeadPosition`: Save a screenshot or toggle the coordinate
+///   overlay or the position of the snake's head.
+/// - `SpeedUp`, `SlowDown`: Change the speed in practice and god mode.
+///
+/// # Methods
+/// ## `from_name`
+/// Looks up an action by the name it has in a keymap file, such as `quit`, `turn-left` or `p2-up`.
+///
+/// ### Returns
+/// `Some(Action)` for a known name, otherwise `None`.
+///
+/// ## `to_command`
+/// Turns the action into the command it issues.
+///
+/// ### Parameters
+/// - `heading`: The direction the first snake is heading in, which relative turns are taken from.
+///
+/// ### Returns
+/// The `Command` to execute.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::command::Command;
+/// # use snake_the_sequel::direction::Direction;
+/// # use snake_the_sequel::keymap::Action;
+/// let action = Action::from_name("turn-left").unwrap();
+/// assert_eq!(action.to_command(Direction::Up), Command::Turn(Direction::Left));
+/// ```
+pub enum Action {
+    Quit,
+    Pause,
+    Up,
+    Right,
+    Down,
+    Left,
+    TurnLeft,
+    TurnRight,
+    PlayerTwoUp,
+    PlayerTwoRight,
+    PlayerTwoDown,
+    PlayerTwoLeft,
+    SpawnFood,
+    Grow,
+    Shrink,
+    Screenshot,
+    Console,
+    ToggleCoordinates,
+    ToggleHeadPosition,
+    SpeedUp,
+    SlowDown
+}
+
+impl Action {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "quit" => Some(Self::Quit),
+            "pause" => Some(Self::Pause),
+            "up" => Some(Self::Up),
+            "right" => Some(Self::Right),
+            "down" => Some(Self::Down),
+            "left" => Some(Self::Left),
+            "turn-left" => Some(Self::TurnLeft),
+            "turn-right" => Some(Self::TurnRight),
+            "p2-up" => Some(Self::PlayerTwoUp),
+            "p2-right" => Some(Self::PlayerTwoRight),
+            "p2-down" => Some(Self::PlayerTwoDown),
+            "p2-left" => Some(Self::PlayerTwoLeft),
+            "spawn-food" => Some(Self::SpawnFood),
+            "grow" => Some(Self::Grow),
+            "shrink" => Some(Self::Shrink),
+            "screenshot" => Some(Self::Screenshot),
+            "console" => Some(Self::Console),
+            "coordinates" => Some(Self::ToggleCoordinates),
+            "head-position" => Some(Self::ToggleHeadPosition),
+            "speed-up" => Some(Self::SpeedUp),
+            "slow-down" => Some(Self::SlowDown),
+            _ => None
+        }
+    }
+
+    pub fn to_command(self, heading: Direction) -> Command {
+        match self {
+            Self::Quit => Command::Quit,
+            Self::Pause => Command::Pause,
+            Self::Up => Command::Turn(Direction::Up),
+            Self::Right => Command::Turn(Direction::Right),
+            Self::Down => Command::Turn(Direction::Down),
+            Self::Left => Command::Turn(Direction::Left),
+            Self::TurnLeft => Command::Turn(heading.counter_clockwise()),
+            Self::TurnRight => Command::Turn(heading.clockwise()),
+            Self::PlayerTwoUp => Command::TurnPlayerTwo(Direction::Up),
+            Self::PlayerTwoRight => Command::TurnPlayerTwo(Direction::Right),
+            Self::PlayerTwoDown => Command::TurnPlayerTwo(Direction::Down),
+            Self::PlayerTwoLeft => Command::TurnPlayerTwo(Direction::Left),
+            Self::SpawnFood => Command::SpawnFood,
+            Self::Grow => Command::Grow,
+            Self::Shrink => Command::Shrink,
+            Self::Screenshot => Command::Screenshot,
+            Self::Console => Command::Console,
+            Self::ToggleCoordinates => Command::ToggleCoordinates,
+            Self::ToggleHeadPosition => Command::ToggleHeadPosition,
+            Self::SpeedUp => Command::SpeedUp,
+            Self::SlowDown => Command::SlowDown
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Represents the bindings of keys to actions.
+///
+/// There are two kinds of bindings. Keys bound by name, like `q` to quit, are matched as they are typed.
+/// The letter keys of a `ControlScheme` are bound by their position on a QWERTY keyboard instead, and
+/// matched after the typed key is translated through the `Layout`, so they stay under the same fingers
+/// on every layout. When a key has both, the keys bound with `bind` win, then the keys of the control
+/// scheme, and the default keys come last: on AZERTY, `ZQSD` steers even though `q` quits by default.
+/// Letters match regardless of case, and `Ctrl+C` always quits, so a keymap can never lock the player in.
+///
+/// Keymap files hold one binding per line, like `quit = q, esc`, with `#` starting a comment. Keys are
+/// single characters or one of `up`, `right`, `down`, `left`, `space`, `esc`, `enter`, `tab`,
+/// `backspace` and `f1` to `f12`. Binding an action replaces its default keys.
+///
+/// # Fields
+/// - `named`: The actions of keys bound by name with `bind`.
+/// - `positional`: The actions of keys bound by their position on a QWERTY keyboard.
+/// - `defaults`: The actions of the default keys, bound by name.
+/// - `layout`: The keyboard layout the positional keys are translated from.
+///
+/// # Methods
+/// ## `new`
+/// Creates a `Keymap` with the default keys: `q` and Esc quit, `p` and space pause, the arrow keys
+/// steer, F5 to F10 issue the debugging commands, the backtick opens the console and `+`/`-`
+/// change the speed. The keys of the control scheme are added on top.
+///
+/// ### Parameters
+/// - `scheme`: The `ControlScheme` adding its letter keys.
+/// - `layout`: The `Layout` of the player's keyboard.
+///
+/// ## `bind`
+/// Binds keys to an action by name, replacing the keys it was bound to before.
+///
+/// ### Parameters
+/// - `action`: The action to bind.
+/// - `keys`: The keys issuing the action.
+///
+/// ## `add_player_two`
+/// Binds `WASD`, by position, to the second snake's directions that have no keys yet.
+///
+/// ## `load`
+/// Reads bindings from a keymap file and applies them on top of the current ones.
+///
+/// ### Parameters
+/// - `path`: The path of the keymap file.
+///
+/// ### Returns
+/// An `io::Result` indicating whether the file could be read and every line is a valid binding.
+///
+/// ## `command_for`
+/// Translates a key press into the command it issues.
+///
+/// ### Parameters
+/// - `event`: The `KeyEvent` read from the terminal.
+/// - `heading`: The direction the first snake is heading in, which relative turns are taken from.
+///
+/// ### Returns
+/// `Some(Command)` if the key is bound, otherwise `None`.
+///
+/// # Example
+/// ```rust
+/// # use crossterm::event::{KeyCode, KeyEvent};
+/// # use snake_the_sequel::command::Command;
+/// # use snake_the_sequel::controls::{ControlScheme, Layout};
+/// # use snake_the_sequel::direction::Direction;
+/// # use snake_the_sequel::keymap::{Action, Keymap};
+/// let mut keymap = Keymap::new(ControlScheme::Vim, Layout::Qwerty);
+/// keymap.bind(Action::Quit, &[KeyCode::Char('x')]);
+/// let command = keymap.command_for(KeyEvent::from(KeyCode::Char('h')), Direction::Up);
+/// assert_eq!(command, Some(Command::Turn(Direction::Left)));
+/// ```
+pub struct Keymap {
+    named: HashMap<KeyCode, Action>,
+    positional: HashMap<KeyCode, Action>,
+    defaults: HashMap<KeyCode, Action>,
+    layout: Layout
+}
+
+impl Keymap {
+    pub fn new(scheme: ControlScheme, layout: Layout) -> Self {
+        let defaults = [
+            (KeyCode::Char('q'), Action::Quit),
+            (KeyCode::Esc, Action::Quit),
+            (KeyCode::Char('p'), Action::Pause),
+            (KeyCode::Char(' '), Action::Pause),
+            (KeyCode::Up, Action::Up),
+            (KeyCode::Right, Action::Right),
+            (KeyCode::Down, Action::Down),
+            (KeyCode::Left, Action::Left),
+            (KeyCode::F(5), Action::SpawnFood),
+            (KeyCode::F(6), Action::Grow),
+            (KeyCode::F(7), Action::Shrink),
+            (KeyCode::F(8), Action::ToggleCoordinates),
+            (KeyCode::F(9), Action::ToggleHeadPosition),
+            (KeyCode::F(10), Action::Screenshot),
+            (KeyCode::Char('`'), Action::Console),
+            (KeyCode::Char('+'), Action::SpeedUp),
+            (KeyCode::Char('='), Action::SpeedUp),
+            (KeyCode::Char('-'), Action::SlowDown)
+        ];
+
+        let positional: &[(char, Action)] = match scheme {
+            ControlScheme::Absolute => &[],
+            ControlScheme::Relative => &[('j', Action::TurnLeft), (',', Action::TurnLeft), ('k', Action::TurnRight), ('.', Action::TurnRight)],
+            ControlScheme::LeftHand => &[('w', Action::Up), ('d', Action::Right), ('s', Action::Down), ('a', Action::Left)],
+            ControlScheme::RightHand => &[('i', Action::Up), ('l', Action::Right), ('k', Action::Down), ('j', Action::Left)],
+            ControlScheme::Vim => &[('k', Action::Up), ('l', Action::Right), ('j', Action::Down), ('h', Action::Left)]
+        };
+
+        Self {
+            named: HashMap::new(),
+            positional: positional.iter().map(|&(key, action)| (KeyCode::Char(key), action)).collect(),
+            defaults: defaults.into_iter().collect(),
+            layout
+        }
+    }
+
+    pub fn bind(&mut self, action: Action, keys: &[KeyCode]) {
+        self.named.retain(|_, bound| *bound != action);
+        self.positional.retain(|_, bound| *bound != action);
+        self.defaults.retain(|_, bound| *bound != action);
+
+        for &key in keys {
+            self.named.insert(normalize(key), action);
+        }
+    }
+
+    pub fn add_player_two(&mut self) {
+        let keys = [('w', Action::PlayerTwoUp), ('d', Action::PlayerTwoRight), ('s', Action::PlayerTwoDown), ('a', Action::PlayerTwoLeft)];
+
+        for (key, action) in keys {
+            let bound = self.named.values().chain(self.positional.values()).chain(self.defaults.values()).any(|&bound| bound == action);
+            if !bound {
+                self.positional.insert(KeyCode::Char(key), action);
+            }
+        }
+    }
+
+    pub fn load(&mut self, path: &Path) -> io::Result<()> {
+        for (action, keys) in load_bindings(path)? {
+            self.bind(action, &keys);
+        }
+
+        Ok(())
+    }
+
+    pub fn command_for(&self, event: KeyEvent, heading: Direction) -> Option<Command> {
+        if event.code == KeyCode::Char('c') && event.modifiers == KeyModifiers::CONTROL {
+            return Some(Command::Quit);
+        }
+
+        let code = normalize(event.code);
+        self.named.get(&code)
+            .or_else(|| self.positional.get(&normalize(self.layout.to_qwerty(code))))
+            .or_else(|| self.defaults.get(&code))
+            .map(|action| action.to_command(heading))
+    }
+}
+
+/// Reads the bindings in a keymap file.
+///
+/// # Parameters
+/// - `path`: The path of the keymap file.
+///
+/// # Returns
+/// The bindings in the order they're in the file, or an error if the file can't be read or a line
+/// isn't a valid binding.
+///
+/// # Example
+/// ```no_run
+/// # use std::path::Path;
+/// # use snake_the_sequel::controls::{ControlScheme, Layout};
+/// # use snake_the_sequel::keymap::{load_bindings, Keymap};
+/// # let mut keymap = Keymap::new(ControlScheme::Absolute, Layout::Qwerty);
+/// for (action, keys) in load_bindings(Path::new("keys.txt"))? {
+///     keymap.bind(action, &keys);
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn load_bindings(path: &Path) -> io::Result<Vec<(Action, Vec<KeyCode>)>> {
+    let contents = fs::read_to_string(path)?;
+    let mut bindings = Vec::new();
+
+    for line in contents.lines() {
+        let binding = line.split('#').next().unwrap_or_default().trim();
+        if binding.is_empty() {
+            continue;
+        }
+
+        match parse_binding(binding) {
+            Some(binding) => bindings.push(binding),
+            None => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("invalid binding: {}", line)))
+        }
+    }
+
+    Ok(bindings)
+}
+
+/// Parses a binding of keys to an action, as written in a keymap file or passed to `--bind`.
+///
+/// # Parameters
+/// - `binding`: The binding, such as `up = k` or `quit=q,esc`.
+///
+/// # Returns
+/// `Some((Action, Vec<KeyCode>))` with the action and its keys, or `None` if the binding isn't valid.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::controls::{ControlScheme, Layout};
+/// # use snake_the_sequel::keymap::{parse_binding, Keymap};
+/// # let mut keymap = Keymap::new(ControlScheme::Absolute, Layout::Qwerty);
+/// let (action, keys) = parse_binding("pause = p, space").unwrap();
+/// keymap.bind(action, &keys);
+/// ```
+pub fn parse_binding(binding: &str) -> Option<(Action, Vec<KeyCode>)> {
+    let (action, keys) = binding.split_once('=')?;
+    let action = Action::from_name(action.trim())?;
+    let keys: Option<Vec<KeyCode>> = keys.split(',').map(|key| parse_key(key.trim())).collect();
+
+    keys.filter(|keys| !keys.is_empty()).map(|keys| (action, keys))
+}
+
+// parses the name of a key, which is either a single character or the name of a special key
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+
+    match name.to_ascii_lowercase().as_str() {
+        "up" => Some(KeyCode::Up),
+        "right" => Some(KeyCode::Right),
+        "down" => Some(KeyCode::Down),
+        "left" => Some(KeyCode::Left),
+        "space" => Some(KeyCode::Char(' ')),
+        "esc" => Some(KeyCode::Esc),
+        "enter" => Some(KeyCode::Enter),
+        "tab" => Some(KeyCode::Tab),
+        "backspace" => Some(KeyCode::Backspace),
+        name => name.strip_prefix('f')
+            .and_then(|number| number.parse().ok())
+            .filter(|number| (1..=12).contains(number))
+            .map(KeyCode::F)
+    }
+}
+
+// letters are bound regardless of case, so they're always looked up in lower case
+fn normalize(code: KeyCode) -> KeyCode {
+    match code {
+        KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+        code => code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(keymap: &Keymap, key: char) -> Option<Command> {
+        keymap.command_for(KeyEvent::from(KeyCode::Char(key)), Direction::Up)
+    }
+
+    #[test]
+    fn scheme_keys_win_over_default_keys_on_other_layouts() {
+        let keymap = Keymap::new(ControlScheme::LeftHand, Layout::Azerty);
+
+        assert_eq!(command(&keymap, 'q'), Some(Command::Turn(Direction::Left)));
+        assert_eq!(command(&keymap, 'z'), Some(Command::Turn(Direction::Up)));
+        assert_eq!(keymap.command_for(KeyEvent::from(KeyCode::Esc), Direction::Up), Some(Command::Quit));
+    }
+
+    #[test]
+    fn player_two_keys_win_over_default_keys_on_other_layouts() {
+        let mut keymap = Keymap::new(ControlScheme::Absolute, Layout::Azerty);
+        keymap.add_player_two();
+
+        assert_eq!(command(&keymap, 'q'), Some(Command::TurnPlayerTwo(Direction::Left)));
+    }
+
+    #[test]
+    fn bound_keys_win_over_scheme_keys() {
+        let mut keymap = Keymap::new(ControlScheme::LeftHand, Layout::Azerty);
+        keymap.bind(Action::Quit, &[KeyCode::Char('q')]);
+
+        assert_eq!(command(&keymap, 'q'), Some(Command::Quit));
+    }
+
+    #[test]
+    fn default_keys_still_apply_on_qwerty() {
+        let keymap = Keymap::new(ControlScheme::LeftHand, Layout::Qwerty);
+
+        assert_eq!(command(&keymap, 'q'), Some(Command::Quit));
+        assert_eq!(command(&keymap, 'a'), Some(Command::Turn(Direction::Left)));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::points::Point;
+
+const SAFE_RADIUS: i32 = 4; // cells around the middle of the board kept free for the snake to start in
+const INSET: u16 = 2; // the two outermost rings are kept free, so the bug can crawl along the edges
+const PILLAR_SPACING: u16 = 3;
+const MAZE_SPACING: u16 = 4;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents one of the built-in level layouts, which put obstacles on the board.
+///
+/// The layouts are scaled to the size of the board. Every layout keeps the middle of the board free
+/// for the snake to start in and the two rings along the edges free for the bug, and never cuts the
+/// board in two, so every free cell can still be reached. On small boards, little of a layout may be left.
+///
+/// # Variants
+/// - `Open`: No obstacles, the classic game.
+/// - `Arena`: A wall around the board, inside the edges, with a gap in the middle of every side.
+/// - `Cross`: A horizontal and a vertical wall crossing in the middle of the board.
+/// - `Pillars`: A grid of single obstacles.
+/// - `Maze`: Vertical walls hanging alternately from the top and standing on the bottom.
+///
+/// # Methods
+/// ## `from_name`
+/// Looks up a level by its name (`open`, `arena`, `cross`, `pillars` or `maze`).
+///
+/// ### Returns
+/// `Some(Level)` for a known name, otherwise `None`.
+///
+/// ## `get_name`
+/// Returns the name of the level, as `from_name` takes it.
+///
+/// ## `obstacles`
+/// Lays the level out on a board.
+///
+/// ### Parameters
+/// - `width`: The width of the board.
+/// - `height`: The height of the board.
+///
+/// ### Returns
+/// The cells taken by obstacles.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::engine::GameEngine;
+/// # use snake_the_sequel::levels::Level;
+/// # let mut engine = GameEngine::new(30, 10);
+/// let level = Level::from_name("maze").unwrap();
+/// engine.set_obstacles(level.obstacles(30, 10));
+/// ```
+pub enum Level {
+    Open,
+    Arena,
+    Cross,
+    Pillars,
+    Maze
+}
+
+impl Level {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "open" => Some(Self::Open),
+            "arena" => Some(Self::Arena),
+            "cross" => Some(Self::Cross),
+            "pillars" => Some(Self::Pillars),
+            "maze" => Some(Self::Maze),
+            _ => None
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Arena => "arena",
+            Self::Cross => "cross",
+            Self::Pillars => "pillars",
+            Self::Maze => "maze"
+        }
+    }
+
+    pub fn obstacles(self, width: u16, height: u16) -> Vec<Point> {
+        // boards too small to keep the edges free don't get any obstacles
+        if width <= INSET * 2 || height <= INSET * 2 {
+            return Vec::new();
+        }
+
+        let (left, top) = (INSET, INSET);
+        let (right, bottom) = (width - 1 - INSET, height - 1 - INSET);
+        let (middle_x, middle_y) = (width / 2, height / 2);
+
+        let points: Vec<Point> = match self {
+            Self::Open => Vec::new(),
+            Self::Arena => {
+                let horizontal = (left..=right).filter(|&x| x.abs_diff(middle_x) > 1)
+                    .flat_map(|x| [Point::new(x, top), Point::new(x, bottom)]);
+                let vertical = (top + 1..bottom).filter(|&y| y.abs_diff(middle_y) > 1)
+                    .flat_map(|y| [Point::new(left, y), Point::new(right, y)]);
+
+                horizontal.chain(vertical).collect()
+            }
+            Self::Cross => {
+                let horizontal = (left..=right).map(|x| Point::new(x, middle_y));
+                let vertical = (top..=bottom).map(|y| Point::new(middle_x, y));
+
+                horizontal.chain(vertical).collect()
+            }
+            Self::Pillars => (top..=bottom).step_by(PILLAR_SPACING as usize)
+                .flat_map(|y| (left..=right).step_by(PILLAR_SPACING as usize).map(move |x| Point::new(x, y)))
+                .collect(),
+            Self::Maze => (left..=right).step_by(MAZE_SPACING as usize)
+                .enumerate()
+                .flat_map(|(i, x)| {
+                    // every other wall leaves its gap at the top instead of the bottom
+                    let ys = if i % 2 == 0 { top..bottom } else { top + 1..bottom + 1 };
+                    ys.map(move |y| Point::new(x, y))
+                })
+                .collect()
+        };
+
+        // the walls of the cross meet in the middle, so the safe area also takes care of the cell they share
+        points.into_iter()
+            .filter(|point| {
+                let distance = (point.x as i32 - middle_x as i32).abs().max((point.y as i32 - middle_y as i32).abs());
+                distance > SAFE_RADIUS
+            })
+            .collect()
+    }
+}
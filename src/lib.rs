@@ -0,0 +1,110 @@
+//! The game logic of Snake the Sequel, a Snake game played in the terminal.
+//!
+//! The `snake_the_sequel` binary only parses the command line and starts a `Game` on the standard
+//! output, so other projects can embed the game and render it to any writer instead. Running a `Game`
+//! still needs a real terminal whatever it renders to, as it reads the keyboard and switches the
+//! terminal to raw mode. The rules of the game live in the `GameEngine`, which doesn't touch the
+//! terminal at all, so it can also be stepped headless, e.g. to simulate runs or train a bot.
+//!
+//! # Modules
+//! - `snake`: Contains the logic for the Snake's movement, growth, and collision detection.
+//! - `direction`: Defines the `Direction` enum representing the four movement directions.
+//! - `game`: Contains the `Game` rendering a `GameEngine` to the terminal and running the game loop.
+//! - `engine`: Contains the `GameEngine` with the game state, including the snake and food, stepped one tick at a time.
+//! - `points`: Defines the `Point` struct, representing coordinates on the grid.
+//! - `command`: Contains the `Command` enum for handling user input.
+//! - `controls`: Contains the `DirectionMap` remapping the direction keys for challenge modifiers.
+//! - `config`: Contains the `Config` with every setting of a game, read from the TOML config file.
+//! - `keymap`: Contains the `Keymap` binding keys to commands, with the defaults, control schemes and rebindings.
+//! - `random`: Contains the `RandomSource` trait through which the game makes its random decisions.
+//! - `effects`: Contains the visual effects drawn around the gameplay, such as the ambient background.
+//! - `macros`: Contains the `InputMacro` struct recording the turns of a run so they can be replayed.
+//! - `scores`: Contains the `HighScores` table kept in the user's data directory.
+//! - `food`: Contains the `Food` struct and the `FoodKind` enum of the kinds of food the snake can eat.
+//! - `powerups`: Contains the `PowerUp` enum and the `ActiveEffect` of a power-up that was picked up.
+//! - `speed`: Contains the `SpeedCurve` turning the speed into the time between ticks, and the `Difficulty` presets.
+//! - `theme`: Contains the `Theme` with the colors and the `Glyphs` the board is drawn with.
+//! - `modes`: Contains the `GameMode` enum of the classic, time attack and survival modes.
+//! - `levels`: Contains the `Level` enum of built-in layouts with obstacles inside the board.
+//! - `crash`: Contains the panic hook restoring the terminal and the crash report written when the game fails.
+//! - `error`: Contains the `GameError` a game stops with when the terminal fails.
+//! - `bug`: Contains the `Bug` struct, the bonus bug crawling along the edges of the board.
+//! - `observer`: Contains the `GameObserver` trait through which other code follows a game, e.g. for sound effects or statistics.
+//! - `autopilot`: Contains the built-in AI steering the snake to the food, for demo mode and headless runs.
+//!
+//! The other modules are only used by the game itself, and hidden from the documentation:
+//! - `profiler`: Contains the `FrameProfiler` collecting frame timings.
+//! - `screenshot`: Contains the `Screenshot` struct, rendering the board offscreen to a PNG image.
+//! - `svg`: Contains the `SvgSnapshot` struct, exporting the board as a vector drawing.
+//! - `rules`: Contains the `Rule` enum of challenge rules added by endless mode.
+//! - `adaptive`: Contains the `AdaptiveDifficulty` model adjusting the speed to the player's performance.
+//! - `console`: Contains the `ConsoleCommand` enum interpreting the commands typed into the developer console.
+//! - `pathfinding`: Contains the breadth-first searches finding the shortest path to the food and the free space around a cell.
+//! - `frame`: Contains the `FrameBuffer` diffing frames so only the changed cells are written to the terminal.
+//! - `menu`: Contains the `Menu` shown before a game, with its `MenuItem` entries.
+//! - `terminal`: Contains the `TerminalGuard` restoring the terminal on every way out of the game, panics included.
+//!
+//! # Example
+//! ```no_run
+//! use snake_the_sequel::engine::{GameEngine, GameStatus};
+//! use snake_the_sequel::config::Config;
+//! use snake_the_sequel::game::Game;
+//!
+//! // render to a buffer instead of the standard output, while the input still comes from the terminal
+//! let mut config = Config::default();
+//! config.practice = true;
+//! let mut game = Game::new(Vec::new(), &config);
+//! game.run()?;
+//!
+//! // or step the game without rendering it at all
+//! let mut engine = GameEngine::new(30, 10);
+//! while engine.step(None) == GameStatus::Running {}
+//! println!("{}", engine.get_score());
+//! # Ok::<(), snake_the_sequel::error::GameError>(())
+//! ```
+
+// rust requires explicit module definitions through use of "mod", though only used in game.rs
+pub mod snake;
+pub mod direction;
+pub mod game;
+pub mod engine;
+pub mod points;
+pub mod command;
+pub mod controls;
+pub mod keymap;
+pub mod config;
+pub mod random;
+pub mod effects;
+pub mod macros;
+pub mod scores;
+pub mod food;
+pub mod powerups;
+pub mod speed;
+pub mod theme;
+pub mod modes;
+pub mod levels;
+pub mod crash;
+pub mod bug;
+pub mod autopilot;
+pub mod observer;
+pub mod error;
+#[doc(hidden)]
+pub mod profiler;
+#[doc(hidden)]
+pub mod screenshot;
+#[doc(hidden)]
+pub mod svg;
+#[doc(hidden)]
+pub mod rules;
+#[doc(hidden)]
+pub mod adaptive;
+#[doc(hidden)]
+pub mod console;
+#[doc(hidden)]
+pub mod pathfinding;
+#[doc(hidden)]
+pub mod frame;
+#[doc(hidden)]
+pub mod terminal;
+#[doc(hidden)]
+pub mod menu;
@@ -0,0 +1,118 @@
+use snake_the_sequel::crash;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::sync::Mutex;
+use std::time::Instant;
+
+pub const LOG_FILE: &str = "snake.log";
+
+#[derive(Debug)]
+/// Represents the logger writing the game's log messages to `LOG_FILE`.
+///
+/// The game takes over the terminal while it runs, so messages are appended to a file in the working
+/// directory instead of being printed. Which messages are written is decided by a filter in the style
+/// of `RUST_LOG`: a comma separated list of directives that are either a level (`debug`), or a module
+/// path and a level (`snake_the_sequel::game=trace`). The most specific directive matching a message
+/// decides whether it's written, and messages no directive matches are dropped.
+///
+/// Independent of the filter, messages up to the `debug` level are also kept in memory for the crash
+/// report, so the logger is installed even when nothing is written to a file.
+///
+/// # Fields
+/// - `file`: The file the messages are appended to (if any).
+/// - `directives`: The module path (if any) and maximum level of every directive in the filter.
+/// - `start`: The moment the logger was created, which the timestamps of the messages are relative to.
+///
+/// # Methods
+/// ## `init`
+/// Installs a `FileLogger` as the global logger, opening `LOG_FILE` if there is a filter.
+///
+/// ### Parameters
+/// - `filter`: The filter deciding which messages are written, e.g. the value of `RUST_LOG`, or `None`
+///   to only keep messages for the crash report.
+///
+/// ### Returns
+/// An `io::Result` indicating whether the log file could be opened.
+///
+/// # Example
+/// ```rust
+/// FileLogger::init(Some("info,snake_the_sequel::game=trace")).unwrap();
+/// log::info!("game started");
+/// ```
+pub struct FileLogger {
+    file: Option<Mutex<File>>,
+    directives: Vec<(Option<String>, LevelFilter)>,
+    start: Instant
+}
+
+impl FileLogger {
+    pub fn init(filter: Option<&str>) -> io::Result<()> {
+        let file = match filter {
+            Some(_) => Some(Mutex::new(OpenOptions::new().create(true).append(true).open(LOG_FILE)?)),
+            None => None
+        };
+        let directives = filter.map(parse_filter).unwrap_or_default();
+        let max_level = directives.iter().map(|&(_, level)| level).max().unwrap_or(LevelFilter::Off);
+
+        let logger = Self { file, directives, start: Instant::now() };
+
+        // a logger can only be installed once, which init is only called for at startup
+        if log::set_logger(Box::leak(Box::new(logger))).is_ok() {
+            log::set_max_level(max_level.max(LevelFilter::Debug));
+        }
+
+        Ok(())
+    }
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.directives.iter()
+            .filter(|(module, _)| module.as_ref().is_none_or(|module| metadata.target().starts_with(module.as_str())))
+            .max_by_key(|(module, _)| module.as_ref().map_or(0, |module| module.len()))
+            .is_some_and(|&(_, level)| metadata.level() <= level)
+    }
+
+    fn log(&self, record: &Record) {
+        let line = format!(
+            "{:>9.3} {:<5} {}: {}",
+            self.start.elapsed().as_secs_f32(),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+
+        if let Some(Ok(mut file)) = self.file.as_ref().map(|file| file.lock()) {
+            if self.enabled(record.metadata()) {
+                let _ = writeln!(file, "{}", line);
+            }
+        }
+
+        if record.level() <= Level::Debug {
+            crash::record_event(line);
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(Ok(mut file)) = self.file.as_ref().map(|file| file.lock()) {
+            let _ = file.flush();
+        }
+    }
+}
+
+// directives that can't be parsed are skipped, like env_logger does
+fn parse_filter(filter: &str) -> Vec<(Option<String>, LevelFilter)> {
+    filter.split(',')
+        .map(|directive| directive.trim())
+        .filter(|directive| !directive.is_empty())
+        .filter_map(|directive| match directive.split_once('=') {
+            Some((module, level)) => level.parse().ok().map(|level| (Some(String::from(module)), level)),
+            None => match directive.parse() {
+                Ok(level) => Some((None, level)),
+                Err(_) => Some((Some(String::from(directive)), LevelFilter::Trace))
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,257 @@
+use crate::config::Config;
+use crate::direction::Direction;
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// the settings a run depends on, in the order of the header
+const SETTINGS: [&str; 17] = [
+    "width", "height", "level", "mode", "players", "speed", "fixed-speed", "curve", "wrap-around", "bounce",
+    "strict", "endless", "shuffle-controls", "fresh-food", "adaptive", "practice", "god"
+];
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// Represents a recording of the turns made during a run.
+///
+/// An `InputMacro` stores the seed the run was played with, the settings the run depends on and every
+/// turn, together with the tick it was made in. Replaying the turns against a game seeded with the same
+/// seed and played with the same settings reproduces the run, which makes it possible to share the
+/// exact steps leading up to a bug.
+///
+/// The file format is plain text: a `seed <seed>` line, a `<setting> <value>` line for each setting
+/// that changes how a run plays out (the board, the level, the mode, the players, the speed and the
+/// rules, such as `wrap-around true`), and a `<tick> <direction>` line for every turn, with the
+/// direction being one of `up`, `right`, `down` or `left`.
+///
+/// # Fields
+/// - `seed`: The seed of the game the turns were recorded in.
+/// - `settings`: The values of the settings the game was played with, in the order of `SETTINGS`.
+/// - `turns`: The turns in the order they were made, each with the tick it was made in.
+///
+/// # Methods
+/// ## `new`
+/// Creates an empty `InputMacro` for a game with the given seed and settings.
+///
+/// ### Parameters
+/// - `seed`: The seed of the game.
+/// - `config`: The settings of the game.
+///
+/// ## `load`
+/// Reads an `InputMacro` from a file.
+///
+/// ### Parameters
+/// - `path`: The path of the file to read.
+///
+/// ### Returns
+/// The `InputMacro`, or an error if the file can't be read or isn't a macro.
+///
+/// ## `save`
+/// Writes the `InputMacro` to a file.
+///
+/// ### Parameters
+/// - `path`: The path of the file to write.
+///
+/// ## `get_seed`
+/// Returns the seed of the game the turns were recorded in.
+///
+/// ## `check`
+/// Checks that a game is played with the settings the turns were recorded with, as the turns only
+/// reproduce the run on the same board at the same speed.
+///
+/// ### Parameters
+/// - `config`: The settings of the game the macro is replayed in.
+///
+/// ### Returns
+/// An error naming the first setting that differs, if any.
+///
+/// ## `record`
+/// Adds a turn to the macro.
+///
+/// ### Parameters
+/// - `tick`: The tick the turn was made in.
+/// - `direction`: The direction the snake turned to.
+///
+/// ## `turns_at`
+/// Returns the turns made in a tick, in the order they were made.
+///
+/// ### Parameters
+/// - `tick`: The tick to look up.
+///
+/// # Example
+/// ```no_run
+/// # use std::path::Path;
+/// # use snake_the_sequel::config::Config;
+/// # use snake_the_sequel::direction::Direction;
+/// # use snake_the_sequel::macros::InputMacro;
+/// let mut recording = InputMacro::new(42, &Config::default());
+/// recording.record(3, Direction::Left);
+/// recording.save(Path::new("bug.macro")).unwrap();
+/// ```
+pub struct InputMacro {
+    seed: u64,
+    settings: Vec<String>,
+    turns: Vec<(u64, Direction)>
+}
+
+impl InputMacro {
+    pub fn new(seed: u64, config: &Config) -> Self {
+        Self { seed, settings: settings(config), turns: Vec::new() }
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let invalid = |line: &str| io::Error::new(io::ErrorKind::InvalidData, format!("invalid macro line: {}", line));
+        let contents = fs::read_to_string(path)?;
+        let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+
+        let first = lines.next().unwrap_or_default();
+        let seed = match first.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["seed", seed] => seed.parse().map_err(|_| invalid(first))?,
+            _ => return Err(invalid(first))
+        };
+
+        let mut settings = Vec::new();
+        for name in SETTINGS {
+            let line = lines.next().unwrap_or_default();
+            match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [setting, value] if *setting == name => settings.push(value.to_string()),
+                _ => return Err(invalid(line))
+            }
+        }
+
+        let mut recording = Self { seed, settings, turns: Vec::new() };
+        for line in lines {
+            let (tick, direction) = match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [tick, direction] => (tick.parse().ok(), parse_direction(direction)),
+                _ => (None, None)
+            };
+
+            match (tick, direction) {
+                (Some(tick), Some(direction)) => recording.record(tick, direction),
+                _ => return Err(invalid(line))
+            }
+        }
+
+        Ok(recording)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn check(&self, config: &Config) -> Result<(), String> {
+        let current = settings(config);
+
+        for ((name, recorded), value) in SETTINGS.iter().zip(&self.settings).zip(&current) {
+            if recorded != value {
+                return Err(format!("recorded with {} {}, not {}", name, recorded, value));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, tick: u64, direction: Direction) {
+        self.turns.push((tick, direction));
+    }
+
+    pub fn turns_at(&self, tick: u64) -> impl Iterator<Item = Direction> + '_ {
+        self.turns.iter()
+            .filter(move |&&(at, _)| at == tick)
+            .map(|&(_, direction)| direction)
+    }
+}
+
+impl fmt::Display for InputMacro {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "seed {}", self.seed)?;
+        for (name, value) in SETTINGS.iter().zip(&self.settings) {
+            writeln!(f, "{} {}", name, value)?;
+        }
+
+        for &(tick, direction) in self.turns.iter() {
+            let name = match direction {
+                Direction::Up => "up",
+                Direction::Right => "right",
+                Direction::Down => "down",
+                Direction::Left => "left"
+            };
+            writeln!(f, "{} {}", tick, name)?;
+        }
+
+        Ok(())
+    }
+}
+
+// the values of the settings a run depends on, in the order of SETTINGS
+fn settings(config: &Config) -> Vec<String> {
+    let curve = config.speed_curve;
+    vec![
+        config.width.to_string(),
+        config.height.to_string(),
+        config.level.get_name().to_string(),
+        config.mode.get_name().to_string(),
+        config.players.to_string(),
+        config.speed.to_string(),
+        config.fixed_speed.map_or(String::from("none"), |speed| speed.to_string()),
+        format!("{},{},{}", curve.get_start().as_millis(), curve.get_acceleration().as_millis(), curve.get_floor().as_millis()),
+        config.wrap_around.to_string(),
+        config.bounce.to_string(),
+        config.strict.to_string(),
+        config.endless.to_string(),
+        config.shuffle_controls.to_string(),
+        config.fresh_food.to_string(),
+        config.adaptive.to_string(),
+        config.practice.to_string(),
+        config.god.to_string()
+    ]
+}
+
+fn parse_direction(name: &str) -> Option<Direction> {
+    match name {
+        "up" => Some(Direction::Up),
+        "right" => Some(Direction::Right),
+        "down" => Some(Direction::Down),
+        "left" => Some(Direction::Left),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modes::GameMode;
+
+    #[test]
+    fn saves_and_loads_the_settings_with_the_turns() {
+        let path = std::env::temp_dir().join(format!("snake-macro-{}.macro", std::process::id()));
+        let mut recording = InputMacro::new(42, &Config::default());
+        recording.record(3, Direction::Left);
+
+        recording.save(&path).unwrap();
+        let loaded = InputMacro::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap(), recording);
+        assert!(recording.to_string().starts_with("seed 42\nwidth 30\nheight 10\nlevel open\nmode classic\nplayers 1\nspeed 1\nfixed-speed none\n"));
+    }
+
+    #[test]
+    fn refuses_other_settings() {
+        let recording = InputMacro::new(42, &Config::default());
+        let mut config = Config::default();
+        assert_eq!(recording.check(&config), Ok(()));
+
+        config.mode = GameMode::Survival;
+        assert_eq!(recording.check(&config), Err(String::from("recorded with mode classic, not survival")));
+
+        config.mode = GameMode::Classic;
+        config.wrap_around = true;
+        assert_eq!(recording.check(&config), Err(String::from("recorded with wrap-around false, not true")));
+    }
+}
@@ -1,33 +1,223 @@
-// rust requires explicit module definitions through use of "mod", though only used in game.rs
-mod snake;
-mod direction;
-mod game;
-mod points;
-mod command;
-
-use crate::game::Game;
+mod cli;
+mod logger;
+
+use crate::cli::{Options, USAGE};
+use crate::logger::{FileLogger, LOG_FILE};
+use snake_the_sequel::config::{self, Config};
+use snake_the_sequel::crash;
+use snake_the_sequel::error::GameError;
+use snake_the_sequel::game::Game;
+use snake_the_sequel::macros::InputMacro;
+use snake_the_sequel::random::SeededRandom;
+use snake_the_sequel::scores;
 use std::io::stdout;
+use std::panic;
+use std::path::Path;
+use std::process;
 
 /// Main entry point for the Snake game.
 ///
-/// This file contains the necessary module imports and initialization for the Snake game.
-/// It starts the game with a terminal UI size of 30 columns and 10 rows.
+/// The game itself lives in the `snake_the_sequel` library, so this file only wires it up to the
+/// standard output and the command line. It starts the game with a terminal UI size of 30 columns and
+/// 10 rows, unless the command line asks for another size.
 ///
 /// # Modules
-/// - `snake`: Contains the logic for the Snake's movement, growth, and collision detection.
-/// - `direction`: Defines the `Direction` enum representing the four movement directions.
-/// - `game`: Manages the game state, including the snake, food, and game loop.
-/// - `points`: Defines the `Point` struct, representing coordinates on the grid.
-/// - `command`: Contains the `Command` enum for handling user input.
+/// - `cli`: Contains the `Options` parsed from the command line and the usage message.
+/// - `logger`: Contains the `FileLogger` writing log messages to `snake.log`, filtered like `RUST_LOG`.
 ///
 /// # Execution
 /// The `main` function initializes a new game and runs it with the specified terminal dimensions (width: 30, height: 10).
+/// It opens on a main menu to start a game, pick the mode, change the difficulty and theme or look at the
+/// high scores, which `--no-menu` skips to start playing straight away, as demo mode and macros always do.
+/// `--width` and `--height` change the size of the board, `--speed <1-20>` the speed it starts at, and
+/// `--no-walls` lets the snake wrap around to the other side of the board. `--level <open|arena|cross|pillars|maze>`
+/// puts walls inside the board that the snake can't cross. `--help` lists every option,
+/// and unknown options or invalid values print the same list. `completions <shell>` prints a completion
+/// script for bash, elvish, fish, powershell or zsh and `man` prints a man page, both listing the same options.
+/// Passing `--profile` prints a histogram of the frame timings when the game ends, while the number of slow
+/// frames and the frame time percentiles are shown in the F8 debug overlay regardless, and `--practice`
+/// starts the game in practice mode where collisions aren't fatal and `+`/`-` change the speed.
+/// `--strict` forbids moving onto the cell the tail is leaving, `--hardcore` makes the score decay over time and `--god` enables god mode for development,
+/// in which the backtick opens a developer console.
+/// `--ambient <rain|stars>` renders an ambient effect in the background of the board, and
+/// `--milestone-borders` changes the border color whenever the snake reaches a length milestone.
+/// `--limited-vision` only renders the cells around and straight ahead of the snake's head, `--mirror`
+/// swaps the left and right controls and `--mirror-all` swaps up and down as well. `--shuffle-controls`
+/// randomly remaps the direction keys every 20 seconds. `--screenshot-on-death` saves a PNG of the board
+/// when the snake dies, which can also be done at any time with F10, and `--export-svg` saves the final
+/// board as an SVG drawing. `--controls relative` steers with `j`/`k` (or `,`/`.`) relative to the snake's
+/// heading instead of only the arrow keys, and `--controls left-hand` or `--controls right-hand` add the
+/// one-handed `WASD` or `IJKL` keys. `--layout <qwerty|azerty|dvorak|colemak>` keeps those keys in the
+/// same place on other keyboard layouts. `--controls vim` steers with `hjkl`. Any key can be rebound with
+/// `--bind <action>=<keys>`, e.g. `--bind quit=x,esc`, or with a `--keymap <file>` holding one such
+/// binding per line, such as `up = k`. `--endless` adds a random challenge rule every 25 points.
+/// `--adaptive` quietly adjusts the speed to how quickly the player reaches the food and how often they crash,
+/// and shows the bug, coins and power-ups more often to a struggling player and less often to one doing well.
+/// `--fixed-speed <1-20>` locks the speed for the whole run instead of increasing it with every food.
+/// `--difficulty <easy|normal|hard|insane>` picks how fast the game starts out and speeds up, and
+/// `--speed-curve <start,acceleration,floor>` sets a curve of its own, in milliseconds between ticks.
+/// `--mode time-attack` scores as much as possible in 2 minutes, counting down in the HUD, and `--mode survival`
+/// starts slow and speeds up every 10 seconds regardless of the food, with a point for every 5 seconds survived.
+/// Each mode keeps its own high score table, and so do games with `--hardcore`, `--fresh-food` or `--bounce`,
+/// e.g. in `highscores-hardcore.json`, as those change how the score adds up.
+/// `--hints` marks the shortest path to the food in practice and god mode.
+/// `--coordinates` numbers the rows and columns along the borders for debugging, which F8 toggles in game.
+/// `--head-position` shows the position of the snake's head in the bottom left corner of the board, which F9 toggles.
+/// `--bounce` turns the snake along walls it runs into, for a point, instead of ending the game.
+/// `--fresh-food` makes food worth up to 5 points, fading to 1 the longer it's left on the board.
+/// If the game crashes or stops with an error, a crash report with the arguments, settings, recent events and last game state is written
+/// to `snake-crash-<time>.txt`. Setting `RUST_LOG` (e.g. `RUST_LOG=debug`) writes log messages to `snake.log`, and `--verbose` logs everything.
+/// `p` or space pauses the game until the next key press, and the game pauses itself after 30 seconds
+/// without input, which `--afk-timeout <seconds>` changes (0 disables it).
+/// The best 10 scores are kept in `highscores.json` in the user's data directory (e.g. `~/.local/share/snake-cli-rs`),
+/// and a run that makes it in asks for the player's name before the table is shown.
+/// `--record-macro` plays a seeded game and saves its turns to a macro file, which `--macro <file>`
+/// replays against a game with the same seed, refusing to if the board, level, mode, players, speed or
+/// rules (like `--no-walls`) differ from the recording. `--seed <number>` plays a seeded game without recording
+/// it, so the same food and bugs show up every time the same seed is played.
+/// Besides the normal food, blue bonus food worth 5 points and red shrink food that removes two segments
+/// show up every now and then, for a short while.
+/// Every option can also be set in `config.toml` in the user's config directory (e.g. `~/.config/snake-cli-rs`),
+/// with the options on the command line overriding it. `--color-snake`, `--color-food` and `--color-border`
+/// (or the `[colors]` section of the config) replace the game's own colors. `--theme <classic|neon|monochrome>`
+/// picks the colors the game uses otherwise, and `--ascii` draws the board with plain ASCII characters
+/// for terminals and fonts without the box-drawing ones.
+/// Power-ups show up now and then as well: `~` slows the game down, `@` lets the snake pass through its
+/// own body and `2` doubles the points, each for 10 seconds, with the time left shown in the top border.
+/// `--players 2` puts a second snake on the board for a second player on the same keyboard, steering
+/// with `WASD` while the first player keeps the arrow keys. The game ends when either snake crashes,
+/// into a wall, itself or the other snake, and the player left standing wins.
+/// `--demo` lets the autopilot play, following the shortest path to the food as long as it leaves the
+/// snake enough room, which makes a good attract mode and stress test.
 ///
 /// # Example
 /// ```rust
-/// // Start a new game with a 30x10 terminal UI
-/// Game::new(stdout(), 30, 10).run();
+/// // Start a new game with the default 30x10 board
+/// Game::new(stdout(), &Config::default()).run()?;
 /// ```
 fn main() {
-    Game::new(stdout(), 30, 10).run(); // stdout, height and width of terminal ui
+    let args: Vec<String> = std::env::args().collect();
+    crash::install(args.clone());
+
+    // the crash report is written once a panic unwound, so it includes the state the game recorded on the way out
+    match panic::catch_unwind(|| start(&args)) {
+        Ok(Ok(())) => {}
+        Ok(Err(error)) => {
+            eprintln!("{}", error);
+            report_crash(Some(&error.to_string()));
+            process::exit(1);
+        }
+        Err(_) => {
+            report_crash(None);
+            process::exit(101);
+        }
+    }
+}
+
+// sets the game up from the command line and the config file and runs it
+fn start(args: &[String]) -> Result<(), GameError> {
+    // the options are checked before the config file is read, so --help and the commands work even if
+    // the file is broken, and only then applied on top of the file
+    let options = parse_options(args, Config::default());
+    if options.help {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    if let Some(generate) = options.generate {
+        if let Err(error) = generate.print(&mut stdout()) {
+            eprintln!("Failed to print: {}", error);
+            process::exit(1);
+        }
+        return Ok(());
+    }
+    let options = parse_options(args, load_config());
+
+    // --verbose logs everything, without having to know the RUST_LOG syntax
+    let filter = if options.verbose {
+        Some(String::from("trace"))
+    } else {
+        std::env::var("RUST_LOG").ok()
+    };
+    if let Err(error) = FileLogger::init(filter.as_deref()) {
+        eprintln!("Failed to open {}: {}", LOG_FILE, error);
+    }
+
+    let playback = options.macro_file.as_deref().map(|path| load_macro(path, &options.config));
+
+    // macros are recorded and replayed against a seeded game, so the same food and bugs appear
+    let seed = match &playback {
+        Some(playback) => Some(playback.get_seed()),
+        None if options.seed.is_some() => options.seed,
+        None if options.record_macro => Some(rand::random()),
+        None => None
+    };
+
+    let mut game = match seed {
+        Some(seed) => Game::with_random(stdout(), &options.config, Box::new(SeededRandom::new(seed))),
+        None => Game::new(stdout(), &options.config)
+    };
+    game.set_profiling(options.profile);
+    game.set_macro_recording(seed.filter(|_| options.record_macro).map(|seed| InputMacro::new(seed, &options.config)));
+    game.set_macro_playback(playback);
+    game.set_high_scores(scores::default_path());
+    crash::record_config(&options.config);
+
+    game.run()?;
+    if let Some(profile) = game.get_profile() {
+        print!("{}", profile);
+    }
+
+    Ok(())
+}
+
+// writes the crash report for the error the game stopped with, or the panic if there is none
+fn report_crash(error: Option<&str>) {
+    match crash::write_report(error) {
+        Ok(name) => eprintln!("Crash report written to {}", name),
+        Err(error) => eprintln!("Failed to write crash report: {}", error)
+    }
+}
+
+// parses the command line on top of the given settings, exiting with an error message and the usage if it's invalid
+fn parse_options(args: &[String], config: Config) -> Options {
+    match Options::parse(args, config) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            process::exit(1);
+        }
+    }
+}
+
+// reads the config file in the user's config directory if there is one, exiting with an error message if it's invalid
+fn load_config() -> Config {
+    let mut config = Config::default();
+
+    if let Some(path) = config::default_path().filter(|path| path.exists()) {
+        if let Err(error) = config.load(&path) {
+            eprintln!("Failed to load config {}: {}", path.display(), error);
+            process::exit(1);
+        }
+    }
+
+    config
+}
+
+// loads the macro passed with --macro, exiting with an error message if it can't be read or was
+// recorded with other settings than the game is played with
+fn load_macro(path: &str, config: &Config) -> InputMacro {
+    let playback = match InputMacro::load(Path::new(path)) {
+        Ok(playback) => playback,
+        Err(error) => {
+            eprintln!("Failed to load macro {}: {}", path, error);
+            process::exit(1);
+        }
+    };
+
+    if let Err(error) = playback.check(config) {
+        eprintln!("Failed to replay macro {}: {}", path, error);
+        process::exit(1);
+    }
+
+    playback
 }
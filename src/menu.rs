@@ -0,0 +1,144 @@
+const MAIN_ITEMS: [MenuItem; 5] = [MenuItem::NewGame, MenuItem::Mode, MenuItem::Options, MenuItem::HighScores, MenuItem::Quit];
+const OPTION_ITEMS: [MenuItem; 4] = [MenuItem::Difficulty, MenuItem::Theme, MenuItem::Ascii, MenuItem::Back];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents an entry of a menu.
+///
+/// # Variants
+/// - `NewGame`: Starts a game.
+/// - `Mode`: Cycles through the game modes.
+/// - `Options`: Opens the options menu.
+/// - `HighScores`: Shows the high score table of the selected mode.
+/// - `Quit`: Leaves the game.
+/// - `Difficulty`: Cycles through the difficulty presets.
+/// - `Theme`: Cycles through the themes.
+/// - `Ascii`: Switches between drawing with ASCII and with box-drawing characters.
+/// - `Back`: Goes back to the main menu.
+///
+/// # Methods
+/// ## `get_label`
+/// Returns the text of the entry, to which the game adds the current value of a setting.
+///
+/// ## `is_setting`
+/// Checks if the entry changes a setting, which the left and right arrow keys cycle through.
+pub enum MenuItem {
+    NewGame,
+    Mode,
+    Options,
+    HighScores,
+    Quit,
+    Difficulty,
+    Theme,
+    Ascii,
+    Back
+}
+
+impl MenuItem {
+    pub fn get_label(self) -> &'static str {
+        match self {
+            Self::NewGame => "New Game",
+            Self::Mode => "Mode",
+            Self::Options => "Options",
+            Self::HighScores => "High Scores",
+            Self::Quit => "Quit",
+            Self::Difficulty => "Difficulty",
+            Self::Theme => "Theme",
+            Self::Ascii => "ASCII",
+            Self::Back => "Back"
+        }
+    }
+
+    pub fn is_setting(self) -> bool {
+        matches!(self, Self::Mode | Self::Difficulty | Self::Theme | Self::Ascii)
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Represents a menu: a title above a list of entries, one of which is selected.
+///
+/// # Fields
+/// - `title`: The text shown above the entries.
+/// - `items`: The entries, from top to bottom.
+/// - `selected`: The index of the selected entry.
+///
+/// # Methods
+/// ## `main`
+/// Creates the main menu shown before a game, with the first entry selected.
+///
+/// ## `options`
+/// Creates the options menu, with the first entry selected.
+///
+/// ## `get_title`
+/// Returns the text shown above the entries.
+///
+/// ## `get_items`
+/// Returns the entries, from top to bottom.
+///
+/// ## `get_selected`
+/// Returns the selected entry.
+///
+/// ## `is_main`
+/// Checks if this is the main menu.
+///
+/// ## `select_next`
+/// Selects the entry below the selected one, going round to the top from the last entry.
+///
+/// ## `select_previous`
+/// Selects the entry above the selected one, going round to the bottom from the first entry.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::menu::{Menu, MenuItem};
+/// let mut menu = Menu::main();
+/// menu.select_next();
+/// assert_eq!(menu.get_selected(), MenuItem::Mode);
+/// ```
+pub struct Menu {
+    title: &'static str,
+    items: &'static [MenuItem],
+    selected: usize
+}
+
+impl Menu {
+    pub fn main() -> Self {
+        Self { title: "SNAKE THE SEQUEL", items: &MAIN_ITEMS, selected: 0 }
+    }
+
+    pub fn options() -> Self {
+        Self { title: "OPTIONS", items: &OPTION_ITEMS, selected: 0 }
+    }
+
+    pub fn get_title(&self) -> &'static str {
+        self.title
+    }
+
+    pub fn get_items(&self) -> &'static [MenuItem] {
+        self.items
+    }
+
+    pub fn get_selected(&self) -> MenuItem {
+        self.items[self.selected]
+    }
+
+    pub fn is_main(&self) -> bool {
+        self.items == MAIN_ITEMS
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % self.items.len();
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+    }
+}
+
+/// Returns the value after (or before) the current one, going round at the ends. A current value that
+/// isn't among the values, like a custom speed curve, is followed by the first one.
+pub fn cycle<T: Copy + PartialEq>(values: &[T], current: T, forward: bool) -> T {
+    match values.iter().position(|value| *value == current) {
+        Some(index) if forward => values[(index + 1) % values.len()],
+        Some(index) => values[(index + values.len() - 1) % values.len()],
+        None => values[0]
+    }
+}
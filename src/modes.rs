@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+const TIME_ATTACK_LIMIT: Duration = Duration::from_secs(120); // the time a time attack lasts
+const SURVIVAL_RAMP: Duration = Duration::from_secs(10); // play time between speed levels in survival
+const SURVIVAL_REWARD: Duration = Duration::from_secs(5); // play time survival awards a point for
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+/// Represents the mode a game is played in, which decides how it speeds up, how it scores and when it ends.
+///
+/// # Variants
+/// - `Classic`: The game speeds up with every food that scores and ends when the snake crashes. The default.
+/// - `TimeAttack`: Score as much as possible before the time runs out, with the time left in the HUD.
+/// - `Survival`: The game speeds up with the play time instead of the food, and every few seconds
+///   survived are worth a point on top of the food.
+///
+/// # Methods
+/// ## `from_name`
+/// Looks up a mode by its name (`classic`, `time-attack` or `survival`).
+///
+/// ### Returns
+/// `Some(GameMode)` for a known name, otherwise `None`.
+///
+/// ## `get_name`
+/// Returns the name of the mode, as `from_name` takes it.
+///
+/// ## `get_time_limit`
+/// Returns the play time after which the game ends, if the mode has one.
+///
+/// ## `get_ramp_interval`
+/// Returns the play time between two speed levels, if the mode speeds up over time instead of with
+/// the food.
+///
+/// ## `get_reward_interval`
+/// Returns the play time the mode awards a point for, if it rewards staying alive.
+///
+/// # Example
+/// ```rust
+/// # use std::time::Duration;
+/// # use snake_the_sequel::config::Config;
+/// # use snake_the_sequel::game::Game;
+/// # use snake_the_sequel::modes::GameMode;
+/// # let mut game = Game::new(Vec::new(), &Config::default());
+/// let mode = GameMode::from_name("time-attack").unwrap();
+/// assert_eq!(mode.get_time_limit(), Some(Duration::from_secs(120)));
+/// game.set_mode(mode);
+/// ```
+pub enum GameMode {
+    #[default]
+    Classic,
+    TimeAttack,
+    Survival
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 3] = [Self::Classic, Self::TimeAttack, Self::Survival];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "classic" => Some(Self::Classic),
+            "time-attack" => Some(Self::TimeAttack),
+            "survival" => Some(Self::Survival),
+            _ => None
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Classic => "classic",
+            Self::TimeAttack => "time-attack",
+            Self::Survival => "survival"
+        }
+    }
+
+    pub fn get_time_limit(&self) -> Option<Duration> {
+        match self {
+            Self::TimeAttack => Some(TIME_ATTACK_LIMIT),
+            _ => None
+        }
+    }
+
+    pub fn get_ramp_interval(&self) -> Option<Duration> {
+        match self {
+            Self::Survival => Some(SURVIVAL_RAMP),
+            _ => None
+        }
+    }
+
+    pub fn get_reward_interval(&self) -> Option<Duration> {
+        match self {
+            Self::Survival => Some(SURVIVAL_REWARD),
+            _ => None
+        }
+    }
+}
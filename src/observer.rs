@@ -0,0 +1,77 @@
+use std::fmt::Debug;
+
+use crate::engine::GameEngine;
+use crate::food::FoodKind;
+use crate::points::Point;
+
+/// Represents something following a game as it's played, such as sound effects, statistics or a
+/// streaming overlay, without having to touch the game loop.
+///
+/// Observers are registered with `Game::add_observer` and called from the game loop, in the order
+/// they were added, right after the engine stepped. Every callback does nothing by default, so an
+/// observer only implements the ones it needs. The callbacks run between two ticks, so they should
+/// return quickly or the game stutters.
+///
+/// # Methods
+/// ## `on_tick`
+/// Called after every step of the engine, once the food eaten and the speed reached are reported.
+///
+/// ### Parameters
+/// - `engine`: The `GameEngine` after the step.
+///
+/// ## `on_food_eaten`
+/// Called when a snake eats a piece of food.
+///
+/// ### Parameters
+/// - `point`: The cell the food was on.
+/// - `kind`: The kind of food.
+/// - `points`: The points the food was worth.
+///
+/// ## `on_speed_up`
+/// Called when the speed goes up, by eating, with the play time in survival mode, or with `+` in
+/// practice mode.
+///
+/// ### Parameters
+/// - `speed`: The speed reached.
+///
+/// ## `on_game_over`
+/// Called when a game ends, because a snake crashed, the time ran out or the player quit.
+///
+/// ### Parameters
+/// - `engine`: The `GameEngine` as the game ended, with the final score.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::config::Config;
+/// # use snake_the_sequel::engine::GameEngine;
+/// # use snake_the_sequel::food::FoodKind;
+/// # use snake_the_sequel::game::Game;
+/// # use snake_the_sequel::observer::GameObserver;
+/// # use snake_the_sequel::points::Point;
+/// # let mut game = Game::new(Vec::new(), &Config::default());
+/// #[derive(Debug, Default)]
+/// struct FoodCounter {
+///     eaten: u32
+/// }
+///
+/// impl GameObserver for FoodCounter {
+///     fn on_food_eaten(&mut self, _point: Point, _kind: FoodKind, _points: u16) {
+///         self.eaten += 1;
+///     }
+///
+///     fn on_game_over(&mut self, engine: &GameEngine) {
+///         eprintln!("{} food for {} points", self.eaten, engine.get_score());
+///     }
+/// }
+///
+/// game.add_observer(Box::new(FoodCounter::default()));
+/// ```
+pub trait GameObserver: Debug {
+    fn on_tick(&mut self, _engine: &GameEngine) {}
+
+    fn on_food_eaten(&mut self, _point: Point, _kind: FoodKind, _points: u16) {}
+
+    fn on_speed_up(&mut self, _speed: u16) {}
+
+    fn on_game_over(&mut self, _engine: &GameEngine) {}
+}
@@ -0,0 +1,139 @@
+use crate::direction::Direction;
+use crate::points::Point;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+pub const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+/// Finds a shortest path between two cells of the grid using a breadth-first search.
+///
+/// # Parameters
+/// - `width`: The width of the game grid.
+/// - `height`: The height of the game grid.
+/// - `start`: The cell the path starts at.
+/// - `goal`: The cell the path should reach.
+/// - `wrap_around`: Whether the path may leave the grid on one side and come back on the other.
+/// - `is_blocked`: Decides whether a cell can't be entered.
+///
+/// # Returns
+/// `Some(Vec<Point>)` with the cells to move through in order, excluding `start` and ending with `goal`,
+/// or `None` if the goal can't be reached.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::pathfinding::shortest_path;
+/// # use snake_the_sequel::points::Point;
+/// let path = shortest_path(30, 10, Point::new(0, 0), Point::new(2, 0), false, |_| false);
+/// assert_eq!(path, Some(vec![Point::new(1, 0), Point::new(2, 0)]));
+/// ```
+pub fn shortest_path(width: u16, height: u16, start: Point, goal: Point, wrap_around: bool, is_blocked: impl Fn(&Point) -> bool) -> Option<Vec<Point>> {
+    let mut previous: HashMap<Point, Point> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(point) = queue.pop_front() {
+        if point == goal {
+            let mut path = vec![goal];
+            while let Some(&before) = previous.get(path.last().unwrap()) {
+                if before == start {
+                    break;
+                }
+                path.push(before);
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        for &direction in DIRECTIONS.iter() {
+            let next = match neighbour(point, direction, width, height, wrap_around) {
+                Some(next) => next,
+                None => continue
+            };
+
+            if next != start && !previous.contains_key(&next) && !is_blocked(&next) {
+                previous.insert(next, point);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Counts the cells that can be reached from a cell of the grid, using a breadth-first search.
+///
+/// # Parameters
+/// - `width`: The width of the game grid.
+/// - `height`: The height of the game grid.
+/// - `start`: The cell to start from, which is counted even if it's blocked.
+/// - `wrap_around`: Whether the search may leave the grid on one side and come back on the other.
+/// - `is_blocked`: Decides whether a cell can't be entered.
+///
+/// # Returns
+/// The number of reachable cells, including `start`.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::pathfinding::count_reachable;
+/// # use snake_the_sequel::points::Point;
+/// let space = count_reachable(30, 10, Point::new(0, 0), false, |point| point.x == 1);
+/// assert_eq!(space, 10);
+/// ```
+pub fn count_reachable(width: u16, height: u16, start: Point, wrap_around: bool, is_blocked: impl Fn(&Point) -> bool) -> usize {
+    let mut visited = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(point) = queue.pop_front() {
+        for &direction in DIRECTIONS.iter() {
+            if let Some(next) = neighbour(point, direction, width, height, wrap_around) {
+                if !visited.contains(&next) && !is_blocked(&next) {
+                    visited.insert(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    visited.len()
+}
+
+/// Returns the cell next to a point in a direction.
+///
+/// # Parameters
+/// - `point`: The cell to start from.
+/// - `direction`: The direction to look in.
+/// - `width`: The width of the game grid.
+/// - `height`: The height of the game grid.
+/// - `wrap_around`: Whether the edges of the grid lead to the other side instead of nowhere.
+///
+/// # Returns
+/// `Some(Point)` with the neighbouring cell, or `None` if that would be outside the grid.
+pub fn neighbour(point: Point, direction: Direction, width: u16, height: u16, wrap_around: bool) -> Option<Point> {
+    match direction {
+        Direction::Up if point.y == 0 => Some(Point::new(point.x, height - 1)).filter(|_| wrap_around),
+        Direction::Right if point.x + 1 >= width => Some(Point::new(0, point.y)).filter(|_| wrap_around),
+        Direction::Down if point.y + 1 >= height => Some(Point::new(point.x, 0)).filter(|_| wrap_around),
+        Direction::Left if point.x == 0 => Some(Point::new(width - 1, point.y)).filter(|_| wrap_around),
+        _ => Some(point.transform(direction, 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_go_through_the_edges_when_wrapping_around() {
+        let (start, goal) = (Point::new(0, 5), Point::new(29, 5));
+
+        assert_eq!(shortest_path(30, 10, start, goal, false, |_| false).map(|path| path.len()), Some(29));
+        assert_eq!(shortest_path(30, 10, start, goal, true, |_| false), Some(vec![goal]));
+    }
+
+    #[test]
+    fn walls_only_close_off_an_area_without_wrapping_around() {
+        let wall = |point: &Point| point.x == 1;
+
+        assert_eq!(count_reachable(30, 10, Point::new(0, 0), false, wall), 10);
+        assert_eq!(count_reachable(30, 10, Point::new(0, 0), true, wall), 290);
+    }
+}
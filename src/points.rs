@@ -24,6 +24,7 @@ use crate::direction::Direction;
 ///
 /// ### Example
 /// ```rust
+/// # use snake_the_sequel::points::Point;
 /// let point = Point::new(5, 10);
 /// assert_eq!(point.x, 5);
 /// assert_eq!(point.y, 10);
@@ -41,6 +42,8 @@ use crate::direction::Direction;
 ///
 /// ### Example
 /// ```rust
+/// # use snake_the_sequel::direction::Direction;
+/// # use snake_the_sequel::points::Point;
 /// let start = Point::new(5, 5);
 /// let moved = start.transform(Direction::Up, 2);
 /// assert_eq!(moved, Point::new(5, 3));
@@ -64,8 +67,11 @@ use crate::direction::Direction;
 ///
 /// ### Example
 /// ```rust
-/// let result = Point::transform_value(10, -5);
-/// assert_eq!(result, 5);
+/// # use snake_the_sequel::direction::Direction;
+/// # use snake_the_sequel::points::Point;
+/// // `transform` moves each coordinate with `transform_value`
+/// let moved = Point::new(10, 4).transform(Direction::Left, 5);
+/// assert_eq!(moved, Point::new(5, 4));
 /// ```
 
 pub struct Point {
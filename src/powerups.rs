@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+const EFFECT_DURATION: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents a power-up the snake can pick up, granting it a temporary effect.
+///
+/// # Variants
+/// - `SlowMotion`: The game runs at half the speed, doubling the time between ticks.
+/// - `Ghost`: The snake passes through its own body instead of biting itself.
+/// - `DoublePoints`: Everything the snake eats or catches is worth twice the points.
+///
+/// # Methods
+/// ## `get_name`
+/// Returns the short name under which the effect is listed on the board.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::powerups::PowerUp;
+/// let power_up = PowerUp::ALL[0];
+/// assert_eq!(power_up.get_name(), "slow");
+/// ```
+pub enum PowerUp {
+    SlowMotion,
+    Ghost,
+    DoublePoints
+}
+
+impl PowerUp {
+    pub const ALL: [PowerUp; 3] = [PowerUp::SlowMotion, PowerUp::Ghost, PowerUp::DoublePoints];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::SlowMotion => "slow",
+            Self::Ghost => "ghost",
+            Self::DoublePoints => "2x"
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents the effect of a power-up that was picked up, for as long as it lasts.
+///
+/// The time left is counted in game time rather than ticks, so an effect lasts just as long however fast
+/// the game is running.
+///
+/// # Fields
+/// - `power_up`: The power-up granting the effect.
+/// - `remaining`: The time left before the effect wears off.
+///
+/// # Methods
+/// ## `new`
+/// Creates a new `ActiveEffect` lasting `EFFECT_DURATION`.
+///
+/// ### Parameters
+/// - `power_up`: The power-up that was picked up.
+///
+/// ## `get_power_up`
+/// Returns the power-up granting the effect.
+///
+/// ## `get_remaining`
+/// Returns the time left before the effect wears off.
+///
+/// ## `elapse`
+/// Counts down the time left.
+///
+/// ### Parameters
+/// - `elapsed`: The time that passed.
+///
+/// ## `is_expired`
+/// Checks if the effect has worn off.
+///
+/// # Example
+/// ```rust
+/// # use std::time::Duration;
+/// # use snake_the_sequel::powerups::{ActiveEffect, PowerUp};
+/// let mut effect = ActiveEffect::new(PowerUp::Ghost);
+/// effect.elapse(Duration::from_secs(4));
+/// println!("{}s left", effect.get_remaining().as_secs());
+/// ```
+pub struct ActiveEffect {
+    power_up: PowerUp,
+    remaining: Duration
+}
+
+impl ActiveEffect {
+    pub fn new(power_up: PowerUp) -> Self {
+        Self { power_up, remaining: EFFECT_DURATION }
+    }
+
+    pub fn get_power_up(&self) -> PowerUp {
+        self.power_up
+    }
+
+    pub fn get_remaining(&self) -> Duration {
+        self.remaining
+    }
+
+    pub fn elapse(&mut self, elapsed: Duration) {
+        self.remaining = self.remaining.saturating_sub(elapsed);
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining.is_zero()
+    }
+}
@@ -0,0 +1,197 @@
+use std::fmt;
+use std::time::Duration;
+
+// upper bounds (in microseconds) of the histogram buckets, the last bucket catches everything slower
+const BUCKET_BOUNDS: [u128; 10] = [250, 500, 1_000, 2_000, 4_000, 8_000, 16_000, 32_000, 64_000, 128_000];
+const MAX_REPORTED_SLOW_FRAMES: usize = 10; // slow frames kept to be listed, the rest are only counted
+const MAX_BAR_WIDTH: u32 = 40;
+
+#[derive(Debug, Default)]
+/// Collects frame timings of the game loop.
+///
+/// The `FrameProfiler` keeps a histogram of how long the simulation step and the render step of
+/// every frame took, and counts the frames in which both steps together exceeded the tick budget
+/// (the interval between two game updates). Only the first `MAX_REPORTED_SLOW_FRAMES` of those are
+/// remembered, so a long game doesn't keep growing the list.
+///
+/// # Fields
+/// - `simulation`: Histogram buckets of the simulation durations.
+/// - `render`: Histogram buckets of the render durations.
+/// - `total`: Histogram buckets of both steps together, which the percentiles are read from.
+/// - `frames`: The total number of recorded frames.
+/// - `slow_frames`: The first frames that exceeded their budget, as `(frame, time spent, budget)`.
+/// - `slow_frame_count`: The number of frames that exceeded their budget.
+///
+/// # Methods
+/// ## `new`
+/// Creates an empty `FrameProfiler`.
+///
+/// ## `record`
+/// Records the timings of a single frame.
+///
+/// ### Parameters
+/// - `simulation`: The time spent updating the game state.
+/// - `render`: The time spent drawing the frame.
+/// - `budget`: The tick interval the frame had to fit in.
+///
+/// ## `slow_frame_count`
+/// Returns the number of frames that exceeded their budget.
+///
+/// ## `percentile`
+/// Returns the time that the given share of the frames took at most, rounded up to the bound of the
+/// histogram bucket it falls in.
+///
+/// ### Parameters
+/// - `percent`: The share of the frames, from `1` to `100`.
+///
+/// ### Returns
+/// The upper bound of the bucket, or `None` if no frames were recorded yet or the share includes frames
+/// slower than the last bound.
+///
+/// ## `summary`
+/// Returns the number of slow frames and the 50th, 95th and 99th percentile of the frame times as a
+/// short line each, for the debug overlay.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::profiler::FrameProfiler;
+/// # use std::time::Duration;
+/// let mut profiler = FrameProfiler::new();
+/// profiler.record(Duration::from_micros(40), Duration::from_millis(3), Duration::from_millis(200));
+/// assert_eq!(profiler.percentile(50), Some(Duration::from_millis(4)));
+/// println!("{}", profiler);
+/// ```
+pub struct FrameProfiler {
+    simulation: [u32; BUCKET_BOUNDS.len() + 1],
+    render: [u32; BUCKET_BOUNDS.len() + 1],
+    total: [u32; BUCKET_BOUNDS.len() + 1],
+    frames: u32,
+    slow_frames: Vec<(u32, Duration, Duration)>,
+    slow_frame_count: usize
+}
+
+impl FrameProfiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, simulation: Duration, render: Duration, budget: Duration) {
+        self.frames += 1;
+        self.simulation[Self::bucket(simulation)] += 1;
+        self.render[Self::bucket(render)] += 1;
+        self.total[Self::bucket(simulation + render)] += 1;
+
+        if simulation + render > budget {
+            self.slow_frame_count += 1;
+            if self.slow_frames.len() < MAX_REPORTED_SLOW_FRAMES {
+                self.slow_frames.push((self.frames, simulation + render, budget));
+            }
+        }
+    }
+
+    pub fn slow_frame_count(&self) -> usize {
+        self.slow_frame_count
+    }
+
+    pub fn percentile(&self, percent: u32) -> Option<Duration> {
+        if self.frames == 0 {
+            return None;
+        }
+
+        // the bucket in which the running count reaches the share of the frames, rounded up
+        let needed = (self.frames as u64 * percent as u64).div_ceil(100);
+        let mut counted = 0;
+        let bucket = self.total.iter().position(|&count| {
+            counted += count as u64;
+            counted >= needed
+        })?;
+
+        BUCKET_BOUNDS.get(bucket).map(|&bound| Duration::from_micros(bound as u64))
+    }
+
+    pub fn summary(&self) -> Vec<String> {
+        let percentiles = [50, 95, 99].iter().map(|&percent| match self.percentile(percent) {
+            Some(bound) => format!("p{}<{}ms", percent, bound.as_micros() as f64 / 1000.0),
+            None if self.frames == 0 => format!("p{} -", percent),
+            None => format!("p{}>{}ms", percent, BUCKET_BOUNDS[BUCKET_BOUNDS.len() - 1] / 1000)
+        });
+
+        std::iter::once(format!("slow {}", self.slow_frame_count)).chain(percentiles).collect()
+    }
+
+    fn bucket(duration: Duration) -> usize {
+        let micros = duration.as_micros();
+        BUCKET_BOUNDS.iter()
+            .position(|&bound| micros < bound)
+            .unwrap_or(BUCKET_BOUNDS.len())
+    }
+
+    fn write_histogram(&self, f: &mut fmt::Formatter, title: &str, buckets: &[u32]) -> fmt::Result {
+        writeln!(f, "{}:", title)?;
+
+        let highest = buckets.iter().copied().max().unwrap_or(0).max(1);
+        for (i, &count) in buckets.iter().enumerate() {
+            let label = match BUCKET_BOUNDS.get(i) {
+                Some(bound) => format!("< {:>7.2}ms", *bound as f64 / 1000.0),
+                None => format!(">= {:>6.2}ms", BUCKET_BOUNDS[BUCKET_BOUNDS.len() - 1] as f64 / 1000.0)
+            };
+            let bar = "#".repeat((count * MAX_BAR_WIDTH / highest) as usize);
+            writeln!(f, "  {} | {:>6} {}", label, count, bar)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for FrameProfiler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Frame profile ({} frames)", self.frames)?;
+        self.write_histogram(f, "Simulation", &self.simulation)?;
+        self.write_histogram(f, "Render", &self.render)?;
+
+        writeln!(f, "Slow frames: {}", self.slow_frame_count())?;
+        for (frame, spent, budget) in &self.slow_frames {
+            writeln!(f, "  frame {:>6}: {:?} spent, {:?} budget", frame, spent, budget)?;
+        }
+        if self.slow_frame_count > self.slow_frames.len() {
+            writeln!(f, "  ... and {} more", self.slow_frame_count - self.slow_frames.len())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_every_slow_frame_but_keeps_only_the_first() {
+        let mut profiler = FrameProfiler::new();
+        for _ in 0..MAX_REPORTED_SLOW_FRAMES * 3 {
+            profiler.record(Duration::from_millis(1), Duration::from_millis(2), Duration::from_millis(2));
+        }
+
+        assert_eq!(profiler.slow_frame_count(), MAX_REPORTED_SLOW_FRAMES * 3);
+        assert_eq!(profiler.slow_frames.len(), MAX_REPORTED_SLOW_FRAMES);
+        assert!(profiler.to_string().contains(&format!("... and {} more", MAX_REPORTED_SLOW_FRAMES * 2)));
+    }
+
+    #[test]
+    fn reads_the_percentiles_from_the_histogram() {
+        let mut profiler = FrameProfiler::new();
+        assert_eq!(profiler.percentile(50), None);
+        assert_eq!(profiler.summary(), ["slow 0", "p50 -", "p95 -", "p99 -"]);
+
+        for _ in 0..98 {
+            profiler.record(Duration::from_micros(100), Duration::from_micros(200), Duration::from_millis(100));
+        }
+        profiler.record(Duration::from_millis(5), Duration::ZERO, Duration::from_millis(100));
+        profiler.record(Duration::from_millis(200), Duration::ZERO, Duration::from_millis(100));
+
+        assert_eq!(profiler.percentile(50), Some(Duration::from_micros(500)));
+        assert_eq!(profiler.percentile(99), Some(Duration::from_millis(8)));
+        assert_eq!(profiler.percentile(100), None);
+        assert_eq!(profiler.summary(), ["slow 1", "p50<0.5ms", "p95<0.5ms", "p99<8ms"]);
+    }
+}
@@ -0,0 +1,68 @@
+use std::fmt::Debug;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Represents a source of random values for the game.
+///
+/// Every random decision that affects gameplay, such as where food appears, goes through a
+/// `RandomSource`, so the game can be driven by something other than the thread's random number
+/// generator: a scripted sequence of values in tests, or a seeded or recorded source to make runs
+/// reproducible.
+///
+/// # Methods
+/// ## `gen_range`
+/// Returns a value between `low` (inclusive) and `high` (exclusive).
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::random::{RandomSource, ThreadRandom};
+/// let mut random = ThreadRandom;
+/// let x = random.gen_range(0, 30);
+/// assert!(x < 30);
+/// ```
+pub trait RandomSource: Debug {
+    fn gen_range(&mut self, low: u16, high: u16) -> u16;
+}
+
+#[derive(Debug)]
+/// Represents the default `RandomSource`, backed by the thread's random number generator.
+pub struct ThreadRandom;
+
+impl RandomSource for ThreadRandom {
+    fn gen_range(&mut self, low: u16, high: u16) -> u16 {
+        rand::thread_rng().gen_range(low, high)
+    }
+}
+
+#[derive(Debug)]
+/// Represents a `RandomSource` that produces the same values for the same seed.
+///
+/// # Methods
+/// ## `new`
+/// Creates a new `SeededRandom`.
+///
+/// ### Parameters
+/// - `seed`: The seed the values are derived from.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::random::{RandomSource, SeededRandom};
+/// let mut random = SeededRandom::new(42);
+/// let x = random.gen_range(0, 30);
+/// ```
+pub struct SeededRandom {
+    rng: StdRng
+}
+
+impl SeededRandom {
+    pub fn new(seed: u64) -> Self {
+        Self { rng: StdRng::seed_from_u64(seed) }
+    }
+}
+
+impl RandomSource for SeededRandom {
+    fn gen_range(&mut self, low: u16, high: u16) -> u16 {
+        self.rng.gen_range(low, high)
+    }
+}
@@ -0,0 +1,41 @@
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents a rule that endless mode layers on top of the game as the score rises.
+///
+/// Every rule corresponds to one of the challenge modifiers that can also be enabled from the
+/// command line, so endless mode only decides when they kick in.
+///
+/// # Variants
+/// - `Strict`: The head may no longer follow onto the cell the tail is leaving.
+/// - `LimitedVision`: Only the cells around and straight ahead of the head are rendered.
+/// - `Mirrored`: The left and right controls are swapped.
+/// - `ShuffledControls`: The direction keys are randomly remapped at regular intervals.
+///
+/// # Methods
+/// ## `get_name`
+/// Returns the short name under which the rule is listed on the board.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::rules::Rule;
+/// let rule = Rule::ALL[0];
+/// assert_eq!(rule.get_name(), "strict");
+/// ```
+pub enum Rule {
+    Strict,
+    LimitedVision,
+    Mirrored,
+    ShuffledControls
+}
+
+impl Rule {
+    pub const ALL: [Rule; 4] = [Rule::Strict, Rule::LimitedVision, Rule::Mirrored, Rule::ShuffledControls];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Strict => "strict",
+            Self::LimitedVision => "vision",
+            Self::Mirrored => "mirror",
+            Self::ShuffledControls => "shuffle"
+        }
+    }
+}
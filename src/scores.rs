@@ -0,0 +1,433 @@
+use std::env;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+use std::str::Chars;
+
+use crate::modes::GameMode;
+
+const MAX_ENTRIES: usize = 10;
+pub const MAX_NAME_LENGTH: usize = 12;
+const DATA_DIR: &str = "snake-cli-rs";
+const FILE_NAME: &str = "highscores.json";
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// Represents a single entry of the high score table.
+///
+/// # Fields
+/// - `name`: The name the player entered.
+/// - `score`: The score of the run.
+///
+/// # Methods
+/// ## `get_name`
+/// Returns the name the player entered.
+///
+/// ## `get_score`
+/// Returns the score of the run.
+pub struct HighScore {
+    name: String,
+    score: u16
+}
+
+impl HighScore {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_score(&self) -> u16 {
+        self.score
+    }
+}
+
+#[derive(Debug, Default)]
+/// Represents the table of the best `MAX_ENTRIES` scores, kept in a JSON file between runs.
+///
+/// The file holds an array with an object for every entry, best score first:
+/// `[{"name": "Robbert", "score": 42}]`. Several games may end at the same time, so the file is locked
+/// while it's read, and while a new score is added to it the table is read again under the same lock
+/// before it's written back, so no score gets lost.
+///
+/// # Fields
+/// - `entries`: The entries of the table, best score first. Equal scores are ordered by age, oldest first.
+///
+/// # Methods
+/// ## `load`
+/// Reads the table from a file, which is empty if the file doesn't exist yet.
+///
+/// ### Parameters
+/// - `path`: The path of the file to read.
+///
+/// ### Returns
+/// The `HighScores`, or an error if the file can't be read or isn't a high score table.
+///
+/// ## `record`
+/// Adds a score to the table in a file, creating the file and its directory if needed.
+///
+/// ### Parameters
+/// - `path`: The path of the file to update.
+/// - `name`: The name of the player.
+/// - `score`: The score of the run.
+///
+/// ### Returns
+/// The updated `HighScores` and the rank the score got (starting at 1), or `None` as rank if the table
+/// filled up with better scores in the meantime.
+///
+/// ## `qualifies`
+/// Checks whether a score would make it into the table.
+///
+/// ### Parameters
+/// - `score`: The score to check.
+///
+/// ### Returns
+/// `true` if the score is above zero and the table isn't full or the score beats its last entry.
+///
+/// ## `insert`
+/// Adds a score to the table, dropping the last entry if the table is full.
+///
+/// ### Returns
+/// The rank the score got (starting at 1), or `None` if it didn't make it into the table.
+///
+/// ## `get_best`
+/// Returns the best score in the table, or `None` if the table is empty.
+///
+/// ## `get_entries`
+/// Returns the entries of the table, best score first.
+///
+/// # Example
+/// ```no_run
+/// # use snake_the_sequel::scores::{self, HighScores};
+/// let path = scores::default_path().unwrap();
+/// if HighScores::load(&path)?.qualifies(42) {
+///     let (table, rank) = HighScores::record(&path, "Robbert", 42)?;
+///     print!("{}", table);
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct HighScores {
+    entries: Vec<HighScore>
+}
+
+impl HighScores {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => return Err(error)
+        };
+
+        file.lock_shared()?;
+        Self::read_from(&file)
+    }
+
+    pub fn record(path: &Path, name: &str, score: u16) -> io::Result<(Self, Option<usize>)> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)?;
+        file.lock()?;
+
+        let mut scores = Self::read_from(&file)?;
+        let rank = scores.insert(name, score);
+
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(scores.to_json().as_bytes())?;
+
+        Ok((scores, rank))
+    }
+
+    pub fn qualifies(&self, score: u16) -> bool {
+        score > 0 && (self.entries.len() < MAX_ENTRIES || self.entries.last().is_some_and(|last| score > last.score))
+    }
+
+    pub fn insert(&mut self, name: &str, score: u16) -> Option<usize> {
+        if !self.qualifies(score) {
+            return None;
+        }
+
+        // a new score goes below older scores that are just as high
+        let index = self.entries.iter().position(|entry| score > entry.score).unwrap_or(self.entries.len());
+        self.entries.insert(index, HighScore { name: String::from(name), score });
+        self.entries.truncate(MAX_ENTRIES);
+
+        Some(index + 1)
+    }
+
+    pub fn get_best(&self) -> Option<u16> {
+        self.entries.first().map(|entry| entry.score)
+    }
+
+    pub fn get_entries(&self) -> &[HighScore] {
+        &self.entries
+    }
+
+    fn read_from(mut file: &File) -> io::Result<Self> {
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        // a file that was just created is still empty
+        if contents.trim().is_empty() {
+            return Ok(Self::default());
+        }
+
+        match parse_entries(&mut contents.chars().peekable()) {
+            Some(entries) => Ok(Self { entries }),
+            None => Err(io::Error::new(io::ErrorKind::InvalidData, "not a high score table"))
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let entries: Vec<String> = self.entries.iter()
+            .map(|entry| format!("  {{\"name\": \"{}\", \"score\": {}}}", escape(&entry.name), entry.score))
+            .collect();
+
+        format!("[\n{}\n]\n", entries.join(",\n"))
+    }
+}
+
+impl fmt::Display for HighScores {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.entries.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(f, "High scores:")?;
+        for (i, entry) in self.entries.iter().enumerate() {
+            writeln!(f, "{:>4}. {:<width$} {:>5}", i + 1, entry.name, entry.score, width = MAX_NAME_LENGTH)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the path of the high score file in the user's data directory, or `None` if there is no home directory.
+pub fn default_path() -> Option<PathBuf> {
+    let data_dir = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library").join("Application Support"))
+    } else {
+        // relative paths in XDG_DATA_HOME are invalid according to the XDG spec, and should be ignored
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")))
+    };
+
+    data_dir.map(|dir| dir.join(DATA_DIR).join(FILE_NAME))
+}
+
+/// Returns the path of the high score file of a game mode and the rules that change the scoring, such as
+/// `hardcore`, next to the file of classic games, which keeps its path. Every other combination gets a
+/// table of its own, e.g. `highscores-survival-hardcore.json`, as their scores don't compare.
+pub fn table_path(path: &Path, mode: GameMode, rules: &[&str]) -> PathBuf {
+    let mode = Some(mode).filter(|mode| *mode != GameMode::Classic).map(|mode| mode.get_name());
+    let name: Vec<&str> = mode.into_iter().chain(rules.iter().copied()).collect();
+
+    if name.is_empty() {
+        path.to_path_buf()
+    } else {
+        path.with_file_name(format!("highscores-{}.json", name.join("-")))
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c)
+        }
+    }
+
+    escaped
+}
+
+// parses the array of entries, which is all there is to the file
+fn parse_entries(chars: &mut Peekable<Chars>) -> Option<Vec<HighScore>> {
+    let mut entries = Vec::new();
+
+    expect(chars, '[')?;
+    if skip_whitespace(chars) == Some(']') {
+        chars.next();
+    } else {
+        loop {
+            entries.push(parse_entry(chars)?);
+            match next_token(chars)? {
+                ',' => continue,
+                ']' => break,
+                _ => return None
+            }
+        }
+    }
+
+    // nothing but whitespace may follow the array
+    match skip_whitespace(chars) {
+        None => Some(entries),
+        Some(_) => None
+    }
+}
+
+fn parse_entry(chars: &mut Peekable<Chars>) -> Option<HighScore> {
+    let mut name = None;
+    let mut score = None;
+
+    expect(chars, '{')?;
+    loop {
+        expect(chars, '"')?;
+        let key = parse_string(chars)?;
+        expect(chars, ':')?;
+
+        match key.as_str() {
+            "name" => {
+                expect(chars, '"')?;
+                name = Some(parse_string(chars)?);
+            }
+            "score" => score = Some(parse_number(chars)?),
+            _ => return None
+        }
+
+        match next_token(chars)? {
+            ',' => continue,
+            '}' => break,
+            _ => return None
+        }
+    }
+
+    Some(HighScore { name: name?, score: score? })
+}
+
+// parses the rest of a string after its opening quote
+fn parse_string(chars: &mut Peekable<Chars>) -> Option<String> {
+    let mut string = String::new();
+
+    loop {
+        match chars.next()? {
+            '"' => return Some(string),
+            '\\' => string.push(match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                'r' => '\r',
+                'b' => '\u{8}',
+                'f' => '\u{c}',
+                'u' => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    char::from_u32(u32::from_str_radix(&code, 16).ok()?)?
+                }
+                c => c
+            }),
+            c => string.push(c)
+        }
+    }
+}
+
+fn parse_number(chars: &mut Peekable<Chars>) -> Option<u16> {
+    skip_whitespace(chars);
+
+    let mut digits = String::new();
+    while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+        digits.push(c);
+        chars.next();
+    }
+
+    digits.parse().ok()
+}
+
+// skips whitespace and consumes the next character, which has to be the expected one
+fn expect(chars: &mut Peekable<Chars>, expected: char) -> Option<()> {
+    next_token(chars).filter(|&c| c == expected).map(|_| ())
+}
+
+fn next_token(chars: &mut Peekable<Chars>) -> Option<char> {
+    skip_whitespace(chars);
+    chars.next()
+}
+
+// skips whitespace and returns the character after it without consuming it
+fn skip_whitespace(chars: &mut Peekable<Chars>) -> Option<char> {
+    while chars.peek().is_some_and(|c| c.is_whitespace()) {
+        chars.next();
+    }
+
+    chars.peek().copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Option<Vec<HighScore>> {
+        parse_entries(&mut json.chars().peekable())
+    }
+
+    #[test]
+    fn escaped_strings_parse_back_to_the_same_text() {
+        for text in ["Robbert", "\"quoted\"", "back\\slash", "tab\tand\nnewline", "bell\u{7}", "snäke 🐍"] {
+            let escaped = format!("{}\"", escape(text));
+            assert_eq!(parse_string(&mut escaped.chars().peekable()).as_deref(), Some(text));
+        }
+    }
+
+    #[test]
+    fn writes_a_table_it_can_read_back() {
+        let mut scores = HighScores::default();
+        scores.insert("a \"b\" c", 12);
+        scores.insert("d\\e", 7);
+
+        assert_eq!(parse(&scores.to_json()), Some(scores.entries));
+        assert_eq!(parse(" [ ] "), Some(Vec::new()));
+    }
+
+    #[test]
+    fn rejects_corrupt_tables() {
+        for json in [
+            "",
+            "{}",
+            "[",
+            "[{\"name\": \"a\", \"score\": 1}",
+            "[{\"name\": \"a\", \"score\": 1},]",
+            "[{\"name\": \"a\"}]",
+            "[{\"name\": \"a\", \"score\": -1}]",
+            "[{\"name\": \"a\", \"score\": 70000}]",
+            "[{\"name\": \"a\", \"score\": 1, \"extra\": 2}]",
+            "[{\"name\": \"a, \"score\": 1}]",
+            "[{\"name\": \"\\u12\"}]",
+            "[] []"
+        ] {
+            assert_eq!(parse(json), None, "{}", json);
+        }
+    }
+
+    #[test]
+    fn inserts_below_equal_scores_and_keeps_the_best() {
+        let mut scores = HighScores::default();
+        assert_eq!(scores.insert("first", 5), Some(1));
+        assert_eq!(scores.insert("second", 5), Some(2));
+        assert_eq!(scores.insert("best", 9), Some(1));
+        assert_eq!(scores.insert("none", 0), None);
+
+        let names: Vec<&str> = scores.get_entries().iter().map(HighScore::get_name).collect();
+        assert_eq!(names, ["best", "first", "second"]);
+
+        for score in 10..20 {
+            scores.insert("filler", score);
+        }
+        assert_eq!(scores.get_entries().len(), MAX_ENTRIES);
+        assert_eq!(scores.insert("low", 10), None);
+        assert_eq!(scores.get_best(), Some(19));
+    }
+
+    #[test]
+    fn keeps_a_table_for_every_mode_and_scoring_rule() {
+        let path = Path::new("/data/highscores.json");
+
+        assert_eq!(table_path(path, GameMode::Classic, &[]), path);
+        assert_eq!(table_path(path, GameMode::Classic, &["hardcore"]), Path::new("/data/highscores-hardcore.json"));
+        assert_eq!(table_path(path, GameMode::Survival, &["fresh-food", "bounce"]), Path::new("/data/highscores-survival-fresh-food-bounce.json"));
+    }
+}
@@ -0,0 +1,116 @@
+use crate::points::Point;
+
+use crossterm::style::Color;
+use image::{ImageResult, Rgb, RgbImage};
+use std::path::Path;
+
+const CELL_SIZE: u32 = 8; // width and height of a single cell in pixels
+const BACKGROUND: Color = Color::Black;
+
+#[derive(Debug)]
+/// Represents an offscreen raster rendering of the board.
+///
+/// The `Screenshot` holds a color for every cell of the board, including the border around it, and
+/// can be saved as a PNG image in which every cell is drawn as a colored square. It only depends on
+/// the logical state of the board, not on what the terminal shows.
+///
+/// # Fields
+/// - `width`: The width of the game grid.
+/// - `height`: The height of the game grid.
+/// - `cells`: The colors of the cells, row by row, including the border.
+///
+/// # Methods
+/// ## `new`
+/// Creates a `Screenshot` of an empty board surrounded by a border.
+///
+/// ### Parameters
+/// - `width`: The width of the game grid.
+/// - `height`: The height of the game grid.
+/// - `border`: The color of the border.
+///
+/// ## `paint`
+/// Colors a cell of the game grid.
+///
+/// ### Parameters
+/// - `point`: A reference to the `Point` of the cell on the game grid.
+/// - `color`: The color to paint the cell in.
+///
+/// ## `save`
+/// Renders the cells to an image and saves it as a PNG file.
+///
+/// ### Parameters
+/// - `path`: The path of the file to write.
+///
+/// ### Returns
+/// An `ImageResult` indicating whether the image was written.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::screenshot::Screenshot;
+/// # use snake_the_sequel::points::Point;
+/// # use crossterm::style::Color;
+/// let mut screenshot = Screenshot::new(30, 10, Color::DarkGrey);
+/// screenshot.paint(&Point::new(3, 4), Color::Green);
+/// screenshot.save(&std::env::temp_dir().join("board.png")).unwrap();
+/// ```
+pub struct Screenshot {
+    width: u16,
+    height: u16,
+    cells: Vec<Color>
+}
+
+impl Screenshot {
+    pub fn new(width: u16, height: u16, border: Color) -> Self {
+        let columns = width as usize + 2;
+        let rows = height as usize + 2;
+
+        let cells = (0..rows)
+            .flat_map(|y| (0..columns).map(move |x| (x, y)))
+            .map(|(x, y)| if x == 0 || y == 0 || x == columns - 1 || y == rows - 1 { border } else { BACKGROUND })
+            .collect();
+
+        Self { width, height, cells }
+    }
+
+    pub fn paint(&mut self, point: &Point, color: Color) {
+        if point.x < self.width && point.y < self.height {
+            let index = (point.y as usize + 1) * (self.width as usize + 2) + point.x as usize + 1;
+            self.cells[index] = color;
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> ImageResult<()> {
+        let columns = self.width as u32 + 2;
+
+        let image = RgbImage::from_fn(columns * CELL_SIZE, (self.height as u32 + 2) * CELL_SIZE, |x, y| {
+            let cell = (y / CELL_SIZE) * columns + x / CELL_SIZE;
+            Rgb(to_rgb(self.cells[cell as usize]))
+        });
+
+        image.save(path)
+    }
+}
+
+/// Converts a terminal color to the RGB value it's commonly displayed as.
+pub fn to_rgb(color: Color) -> [u8; 3] {
+    match color {
+        Color::Black => [0, 0, 0],
+        Color::DarkGrey => [85, 85, 85],
+        Color::Red => [255, 85, 85],
+        Color::DarkRed => [170, 0, 0],
+        Color::Green => [85, 255, 85],
+        Color::DarkGreen => [0, 170, 0],
+        Color::Yellow => [255, 255, 85],
+        Color::DarkYellow => [170, 85, 0],
+        Color::Blue => [85, 85, 255],
+        Color::DarkBlue => [0, 0, 170],
+        Color::Magenta => [255, 85, 255],
+        Color::DarkMagenta => [170, 0, 170],
+        Color::Cyan => [85, 255, 255],
+        Color::DarkCyan => [0, 170, 170],
+        Color::White => [255, 255, 255],
+        Color::Grey => [170, 170, 170],
+        Color::Rgb { r, g, b } => [r, g, b],
+        _ => [170, 170, 170]
+    }
+}
@@ -1,6 +1,10 @@
 use crate::direction::Direction;
 use crate::points::Point;
 
+use std::collections::{HashMap, VecDeque};
+
+const MIN_LENGTH: usize = 2;
+
 #[derive(Debug)]
 
 /// Represents the snake in the Snake game.
@@ -9,9 +13,12 @@ use crate::points::Point;
 /// including its body, direction, and growth status.
 ///
 /// # Fields
-/// - `body`: A vector of `Point` representing the positions of the snake's segments.
+/// - `body`: A double-ended queue of `Point` representing the positions of the snake's segments, head first,
+///   so moving only touches both ends.
+/// - `occupied`: The number of segments on every cell of `body`, so checking whether a cell is taken doesn't
+///   have to scan the whole body. A ghost snake can cross itself, so a cell may hold more than one segment.
 /// - `direction`: The current direction of the snake's movement.
-/// - `digesting`: Indicates whether the snake is in a growth state (e.g., after eating food).
+/// - `pending_growth`: The number of segments the snake still has to grow, one on every move (e.g., after eating food).
 ///
 /// # Methods
 /// ## `new`
@@ -27,6 +34,9 @@ use crate::points::Point;
 ///
 /// ### Example
 /// ```rust
+/// # use snake_the_sequel::direction::Direction;
+/// # use snake_the_sequel::points::Point;
+/// # use snake_the_sequel::snake::Snake;
 /// let start = Point::new(5, 5);
 /// let snake = Snake::new(start, 3, Direction::Right);
 /// ```
@@ -37,11 +47,26 @@ use crate::points::Point;
 /// ### Returns
 /// A `Point` representing the head's position.
 ///
+/// ## `get_tail_point`
+/// Returns the position of the snake's last segment.
+///
+/// ### Returns
+/// A `Point` representing the tail's position.
+///
 /// ## `get_body_points`
-/// Returns a vector of points representing the snake's body.
+/// Returns the points of the snake's body, head first, without copying them.
 ///
 /// ### Returns
-/// A `Vec<Point>` containing all body segment positions.
+/// A reference to the `VecDeque<Point>` containing all body segment positions.
+///
+/// ## `len`
+/// Returns the number of segments of the snake, without the ones it still has to grow.
+///
+/// ## `is_empty`
+/// Checks if the snake has no segments, which a snake created with a length of at least 1 never has.
+///
+/// ## `get_pending_growth`
+/// Returns the number of segments the snake still has to grow.
 ///
 /// ## `get_direction`
 /// Returns the current direction of the snake.
@@ -49,8 +74,14 @@ use crate::points::Point;
 /// ### Returns
 /// A `Direction` indicating the snake's movement direction.
 ///
+/// ## `is_digesting`
+/// Checks if the snake will grow on its next move.
+///
+/// ### Returns
+/// `true` if the snake keeps its tail on the next move, as it still has segments to grow, otherwise `false`.
+///
 /// ## `contains_point`
-/// Checks if the snake's body contains a specific point.
+/// Checks if the snake's body contains a specific point, in constant time.
 ///
 /// ### Parameters
 /// - `point`: A reference to a `Point` to check.
@@ -59,10 +90,14 @@ use crate::points::Point;
 /// `true` if the point is part of the snake's body, otherwise `false`.
 ///
 /// ## `slither`
-/// Moves the snake forward by one step in its current direction.
+/// Moves the snake forward by one step, onto the cell in front of its head.
 ///
-/// - If `digesting` is `true`, the snake grows and does not remove its last segment.
-/// - If `digesting` is `false`, the snake moves normally, and its tail segment is removed.
+/// - If the snake has segments to grow, it grows one and does not remove its last segment.
+/// - Otherwise, the snake moves normally, and its tail segment is removed.
+///
+/// ### Parameters
+/// - `head`: The cell in front of the head, which the game decides as it may lie on the other side of
+///   the board when the snake wraps around.
 ///
 /// ## `set_direction`
 /// Updates the snake's direction.
@@ -71,12 +106,39 @@ use crate::points::Point;
 /// - `direction`: The new `Direction` for the snake.
 ///
 /// ## `grow`
-/// Marks the snake for growth, adding an additional segment after its next move.
+/// Marks the snake for growth, adding a segment after each of its next moves until it grew them all.
+/// Growing again before that adds to the segments still to grow.
+///
+/// ### Parameters
+/// - `amount`: The number of segments to grow.
+///
+/// ## `shrink`
+/// Removes the last segment of the snake, as long as it stays at least `MIN_LENGTH` segments long.
+///
+/// ## `teleport`
+/// Moves the whole snake, keeping its shape, so that its head ends up at the given point. The caller
+/// is responsible for making sure the body stays within the grid.
+///
+/// ### Parameters
+/// - `head`: The new position of the snake's head.
+///
+/// ## `occupy`
+/// Counts a segment on a cell.
+///
+/// ### Parameters
+/// - `point`: The cell the segment is on.
+///
+/// ## `vacate`
+/// Stops counting a segment on a cell, which is free again once its last segment left.
+///
+/// ### Parameters
+/// - `point`: The cell the segment was on.
 
 pub struct Snake {
-    body: Vec<Point>,
+    body: VecDeque<Point>,
+    occupied: HashMap<Point, u16>,
     direction: Direction,
-    digesting: bool,
+    pending_growth: u16,
 }
 
 impl Snake {
@@ -84,48 +146,109 @@ impl Snake {
     pub fn new(start: Point, length: u16, direction: Direction) -> Self {
         let opposite = direction.opposite();
 
-        let body: Vec<Point> = (0..length)
+        let body: VecDeque<Point> = (0..length)
         .into_iter()  
         .map(|i| start.transform(opposite, i))
         .collect();
 
-        Self { body, direction, digesting: false }
+        let mut snake = Self { body, occupied: HashMap::new(), direction, pending_growth: 0 };
+        for point in snake.body.clone() {
+            snake.occupy(point);
+        }
+
+        snake
     }
 
     pub fn get_head_point(&self) -> Point {
-        self.body.first().unwrap().clone() 
+        *self.body.front().unwrap()
     }
 
-    pub fn get_body_points(&self) -> Vec<Point> {
-        self.body.clone()
+    pub fn get_tail_point(&self) -> Point {
+        *self.body.back().unwrap()
+    }
+
+    pub fn get_body_points(&self) -> &VecDeque<Point> {
+        &self.body
+    }
+
+    pub fn len(&self) -> usize {
+        self.body.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.body.is_empty()
+    }
+
+    pub fn get_pending_growth(&self) -> u16 {
+        self.pending_growth
     }
 
     pub fn get_direction(&self) -> Direction { 
         self.direction
     }
 
-    pub fn contains_point(&self, point: &Point) -> bool {
-        self.body.contains(point)
+    pub fn is_digesting(&self) -> bool {
+        self.pending_growth > 0
     }
 
-    pub fn slither(&mut self) {
-
-        self.body.insert(0, self.body.first().unwrap().transform(self.direction, 1)); 
+    pub fn contains_point(&self, point: &Point) -> bool {
+        self.occupied.contains_key(point)
+    }
 
-        // if digesting is true, we don't remove the newly added block
-        if !self.digesting {
-            self.body.remove(self.body.len() - 1);
+    pub fn slither(&mut self, head: Point) {
+        // while digesting, we don't remove the last block. The tail is removed before the head is
+        // added, as the head may move onto the cell the tail leaves
+        if self.pending_growth == 0 {
+            let tail = self.body.pop_back().unwrap();
+            self.vacate(tail);
         }
         else {
-            self.digesting = false;
+            self.pending_growth -= 1;
         }
+
+        self.body.push_front(head);
+        self.occupy(head);
     }
 
     pub fn set_direction(&mut self, direction: Direction) {
         self.direction = direction;
     }
 
-    pub fn grow(&mut self) {
-        self.digesting = true;
+    pub fn grow(&mut self, amount: u16) {
+        self.pending_growth = self.pending_growth.saturating_add(amount);
+    }
+
+    pub fn shrink(&mut self) {
+        if self.body.len() > MIN_LENGTH {
+            let tail = self.body.pop_back().unwrap();
+            self.vacate(tail);
+        }
+    }
+
+    pub fn teleport(&mut self, head: Point) {
+        let old_head = self.get_head_point();
+        let dx = head.x as i32 - old_head.x as i32;
+        let dy = head.y as i32 - old_head.y as i32;
+
+        for point in self.body.iter_mut() {
+            *point = Point::new((point.x as i32 + dx) as u16, (point.y as i32 + dy) as u16);
+        }
+        self.occupied.clear();
+        for point in self.body.clone() {
+            self.occupy(point);
+        }
+    }
+
+    fn occupy(&mut self, point: Point) {
+        *self.occupied.entry(point).or_insert(0) += 1;
+    }
+
+    fn vacate(&mut self, point: Point) {
+        if let Some(count) = self.occupied.get_mut(&point) {
+            *count -= 1;
+            if *count == 0 {
+                self.occupied.remove(&point);
+            }
+        }
     }
 }
\ No newline at end of file
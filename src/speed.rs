@@ -0,0 +1,225 @@
+use std::time::Duration;
+
+const EASY: SpeedCurve = SpeedCurve::from_millis(800, 30, 260);
+const NORMAL: SpeedCurve = SpeedCurve::from_millis(675, 25, 200); // the curve the game always had
+const HARD: SpeedCurve = SpeedCurve::from_millis(450, 20, 120);
+const INSANE: SpeedCurve = SpeedCurve::from_millis(250, 10, 60);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents how the time between two ticks shrinks as the game speeds up.
+///
+/// The game starts at a speed level, 1 (slowest) unless chosen otherwise, and goes up a level with every
+/// food that scores, up to `MAX_SPEED`. At speed 1 the curve gives its starting interval, every level
+/// above it takes the acceleration off, and the interval never drops below the floor. The presets reach
+/// their floor at or just before `MAX_SPEED`, so every level up to there makes the game faster.
+///
+/// # Fields
+/// - `start`: The time between ticks at speed 1.
+/// - `acceleration`: The time taken off the interval for every speed level.
+/// - `floor`: The shortest time between ticks.
+///
+/// # Methods
+/// ## `new`
+/// Creates a new `SpeedCurve`.
+///
+/// ### Parameters
+/// - `start`: The time between ticks at speed 1.
+/// - `acceleration`: The time taken off the interval for every speed level.
+/// - `floor`: The shortest time between ticks, which is also used if it's above `start`.
+///
+/// ## `from_millis`
+/// Creates a new `SpeedCurve` from milliseconds, as the presets are defined.
+///
+/// ## `parse`
+/// Reads a curve written as its start, acceleration and floor in milliseconds, such as `675,25,200`.
+///
+/// ### Returns
+/// `Some(SpeedCurve)` for a valid curve, otherwise `None`.
+///
+/// ## `get_start`
+/// Returns the time between ticks at speed 1.
+///
+/// ## `get_acceleration`
+/// Returns the time taken off the interval for every speed level.
+///
+/// ## `get_floor`
+/// Returns the shortest time between ticks.
+///
+/// ## `interval`
+/// Returns the time between ticks at a speed level.
+///
+/// ### Parameters
+/// - `speed`: The speed level, starting at 1. Level 0 is treated as 1.
+///
+/// # Example
+/// ```rust
+/// # use std::time::Duration;
+/// # use snake_the_sequel::speed::SpeedCurve;
+/// let curve = SpeedCurve::parse("500,20,100").unwrap();
+/// assert_eq!(curve.interval(1), Duration::from_millis(500));
+/// assert_eq!(curve.interval(11), Duration::from_millis(300));
+/// assert_eq!(curve.interval(50), Duration::from_millis(100));
+/// ```
+pub struct SpeedCurve {
+    start: Duration,
+    acceleration: Duration,
+    floor: Duration
+}
+
+impl SpeedCurve {
+    pub fn new(start: Duration, acceleration: Duration, floor: Duration) -> Self {
+        Self { start, acceleration, floor }
+    }
+
+    pub const fn from_millis(start: u64, acceleration: u64, floor: u64) -> Self {
+        Self {
+            start: Duration::from_millis(start),
+            acceleration: Duration::from_millis(acceleration),
+            floor: Duration::from_millis(floor)
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let millis: Vec<u64> = text.split(',').map(|part| part.trim().parse().ok()).collect::<Option<_>>()?;
+
+        match millis[..] {
+            // a floor of zero would let the game run without any delay at all
+            [start, acceleration, floor] if floor > 0 => Some(Self::from_millis(start, acceleration, floor)),
+            _ => None
+        }
+    }
+
+    pub fn get_start(&self) -> Duration {
+        self.start
+    }
+
+    pub fn get_acceleration(&self) -> Duration {
+        self.acceleration
+    }
+
+    pub fn get_floor(&self) -> Duration {
+        self.floor
+    }
+
+    pub fn interval(&self, speed: u16) -> Duration {
+        let levels = speed.saturating_sub(1) as u32;
+        self.start.saturating_sub(self.acceleration.saturating_mul(levels)).max(self.floor)
+    }
+}
+
+impl Default for SpeedCurve {
+    fn default() -> Self {
+        NORMAL
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents one of the difficulty presets, each with its own `SpeedCurve`.
+///
+/// # Variants
+/// - `Easy`: Starts slow and speeds up gently.
+/// - `Normal`: The classic curve.
+/// - `Hard`: Starts faster and gets faster still.
+/// - `Insane`: Barely leaves time to react.
+///
+/// # Methods
+/// ## `from_name`
+/// Looks up a difficulty by its name (`easy`, `normal`, `hard` or `insane`).
+///
+/// ### Returns
+/// `Some(Difficulty)` for a known name, otherwise `None`.
+///
+/// ## `from_curve`
+/// Looks up the preset a `SpeedCurve` belongs to.
+///
+/// ### Returns
+/// `Some(Difficulty)` for the curve of a preset, otherwise `None`.
+///
+/// ## `get_name`
+/// Returns the name of the preset, as `from_name` takes it.
+///
+/// ## `curve`
+/// Returns the `SpeedCurve` of the preset.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::config::Config;
+/// # use snake_the_sequel::game::Game;
+/// # use snake_the_sequel::speed::Difficulty;
+/// # let mut game = Game::new(Vec::new(), &Config::default());
+/// let difficulty = Difficulty::from_name("hard").unwrap();
+/// game.set_speed_curve(difficulty.curve());
+/// ```
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+    Insane
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [Self::Easy, Self::Normal, Self::Hard, Self::Insane];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "easy" => Some(Self::Easy),
+            "normal" => Some(Self::Normal),
+            "hard" => Some(Self::Hard),
+            "insane" => Some(Self::Insane),
+            _ => None
+        }
+    }
+
+    pub fn from_curve(curve: SpeedCurve) -> Option<Self> {
+        Self::ALL.into_iter().find(|difficulty| difficulty.curve() == curve)
+    }
+
+    pub fn get_name(self) -> &'static str {
+        match self {
+            Self::Easy => "easy",
+            Self::Normal => "normal",
+            Self::Hard => "hard",
+            Self::Insane => "insane"
+        }
+    }
+
+    pub fn curve(self) -> SpeedCurve {
+        match self {
+            Self::Easy => EASY,
+            Self::Normal => NORMAL,
+            Self::Hard => HARD,
+            Self::Insane => INSANE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::MAX_SPEED;
+
+    #[test]
+    fn every_level_of_a_preset_is_faster_until_the_floor() {
+        for difficulty in Difficulty::ALL {
+            let curve = difficulty.curve();
+            assert_eq!(curve.interval(1), curve.get_start());
+
+            for speed in 2..=MAX_SPEED {
+                let (slower, faster) = (curve.interval(speed - 1), curve.interval(speed));
+                assert!(faster < slower || faster == curve.get_floor(), "{:?} at speed {}", difficulty, speed);
+            }
+            assert!(curve.interval(MAX_SPEED) < curve.interval(MAX_SPEED / 2), "{:?}", difficulty);
+        }
+    }
+
+    #[test]
+    fn parses_a_curve_of_its_own() {
+        let curve = SpeedCurve::parse("500, 20, 100").unwrap();
+
+        assert_eq!(curve.interval(1), Duration::from_millis(500));
+        assert_eq!(curve.interval(11), Duration::from_millis(300));
+        assert_eq!(curve.interval(50), Duration::from_millis(100));
+        assert_eq!(SpeedCurve::parse("500,20,0"), None);
+        assert_eq!(SpeedCurve::parse("500,20"), None);
+    }
+}
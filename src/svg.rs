@@ -0,0 +1,171 @@
+use crate::points::Point;
+use crate::screenshot::to_rgb;
+
+use crossterm::style::Color;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const CELL_SIZE: u32 = 20; // width and height of a single cell in SVG units
+const BACKGROUND: Color = Color::Black;
+
+#[derive(Debug)]
+/// Represents a vector drawing of the board.
+///
+/// The `SvgSnapshot` collects the elements on the board as SVG shapes: the snake as a line
+/// through the centers of its segments, food as dots and other entities as filled cells, all inside
+/// the border of the board. Its `Display` implementation produces the SVG document.
+///
+/// # Fields
+/// - `width`: The width of the game grid.
+/// - `height`: The height of the game grid.
+/// - `border`: The color of the border.
+/// - `elements`: The SVG elements drawn on the board.
+///
+/// # Methods
+/// ## `new`
+/// Creates an `SvgSnapshot` of an empty board.
+///
+/// ### Parameters
+/// - `width`: The width of the game grid.
+/// - `height`: The height of the game grid.
+/// - `border`: The color of the border.
+///
+/// ## `add_path`
+/// Draws a line through a sequence of cells, with a dot marking the first one. The line is broken
+/// wherever two consecutive cells aren't next to each other, e.g. where the snake wraps around the board.
+///
+/// ## `add_dot`
+/// Draws a dot in the middle of a cell.
+///
+/// ## `add_cell`
+/// Fills a cell.
+///
+/// ## `save`
+/// Writes the SVG document to a file.
+///
+/// ### Parameters
+/// - `path`: The path of the file to write.
+///
+/// ### Returns
+/// An `io::Result` indicating whether the file was written.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::svg::SvgSnapshot;
+/// # use snake_the_sequel::points::Point;
+/// # use crossterm::style::Color;
+/// let mut svg = SvgSnapshot::new(30, 10, Color::DarkGrey);
+/// svg.add_path(&[Point::new(3, 3), Point::new(2, 3)], Color::Green);
+/// svg.add_dot(&Point::new(8, 5), Color::White);
+/// svg.save(&std::env::temp_dir().join("board.svg")).unwrap();
+/// ```
+pub struct SvgSnapshot {
+    width: u16,
+    height: u16,
+    border: Color,
+    elements: Vec<String>
+}
+
+impl SvgSnapshot {
+    pub fn new(width: u16, height: u16, border: Color) -> Self {
+        Self { width, height, border, elements: Vec::new() }
+    }
+
+    pub fn add_path<'a>(&mut self, points: impl IntoIterator<Item = &'a Point>, color: Color) {
+        let points: Vec<&Point> = points.into_iter().collect();
+
+        // a snake wrapping around the board continues on the other side, so its line is broken there
+        let mut start = 0;
+        for end in 1..=points.len() {
+            if end == points.len() || !Self::is_adjacent(points[end - 1], points[end]) {
+                self.add_polyline(&points[start..end], color);
+                start = end;
+            }
+        }
+
+        if let Some(&point) = points.first() {
+            let (x, y) = Self::center(point);
+            self.elements.push(format!(r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#, x, y, CELL_SIZE * 2 / 5, Self::hex(color)));
+        }
+    }
+
+    pub fn add_dot(&mut self, point: &Point, color: Color) {
+        let (x, y) = Self::center(point);
+        self.elements.push(format!(r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#, x, y, CELL_SIZE / 4, Self::hex(color)));
+    }
+
+    pub fn add_cell(&mut self, point: &Point, color: Color) {
+        self.elements.push(format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            (point.x as u32 + 1) * CELL_SIZE, (point.y as u32 + 1) * CELL_SIZE, CELL_SIZE, CELL_SIZE, Self::hex(color)
+        ));
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    fn add_polyline(&mut self, points: &[&Point], color: Color) {
+        let coordinates: Vec<String> = points.iter()
+            .map(|point| Self::center(point))
+            .map(|(x, y)| format!("{},{}", x, y))
+            .collect();
+
+        self.elements.push(format!(
+            r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round"/>"#,
+            coordinates.join(" "), Self::hex(color), CELL_SIZE * 3 / 5
+        ));
+    }
+
+    fn is_adjacent(a: &Point, b: &Point) -> bool {
+        a.x.abs_diff(b.x) + a.y.abs_diff(b.y) == 1
+    }
+
+    // the board is surrounded by a border of one cell wide
+    fn center(point: &Point) -> (u32, u32) {
+        ((point.x as u32 + 1) * CELL_SIZE + CELL_SIZE / 2, (point.y as u32 + 1) * CELL_SIZE + CELL_SIZE / 2)
+    }
+
+    fn hex(color: Color) -> String {
+        let [r, g, b] = to_rgb(color);
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+impl fmt::Display for SvgSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = (self.width as u32 + 2) * CELL_SIZE;
+        let height = (self.height as u32 + 2) * CELL_SIZE;
+
+        writeln!(f, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#, width, height, width, height)?;
+        writeln!(f, r#"  <rect width="{}" height="{}" fill="{}"/>"#, width, height, Self::hex(self.border))?;
+        writeln!(
+            f, r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            CELL_SIZE, CELL_SIZE, self.width as u32 * CELL_SIZE, self.height as u32 * CELL_SIZE, Self::hex(BACKGROUND)
+        )?;
+
+        for element in self.elements.iter() {
+            writeln!(f, "  {}", element)?;
+        }
+
+        writeln!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breaks_the_path_where_it_wraps_around() {
+        let mut svg = SvgSnapshot::new(10, 5, Color::DarkGrey);
+        svg.add_path(&[Point::new(0, 2), Point::new(9, 2), Point::new(8, 2), Point::new(8, 3)], Color::Green);
+
+        let polylines: Vec<&String> = svg.elements.iter().filter(|element| element.starts_with("<polyline")).collect();
+        assert_eq!(polylines.len(), 2);
+        assert!(polylines[0].contains(r#"points="30,70""#));
+        assert!(polylines[1].contains(r#"points="210,70 190,70 190,90""#));
+    }
+}
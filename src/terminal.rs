@@ -0,0 +1,74 @@
+use std::io::stdout;
+
+use crossterm::ExecutableCommand;
+use crossterm::cursor::{Hide, Show};
+use crossterm::style::ResetColor;
+use crossterm::terminal::{Clear, ClearType, SetSize, disable_raw_mode, enable_raw_mode, size};
+
+use crate::error::GameError;
+
+#[derive(Debug)]
+/// Represents the terminal while the game has it in raw mode, restoring it when dropped.
+///
+/// The terminal is restored on every way out of the game: when it ends normally, when an error is
+/// returned and while unwinding from a panic. Restoring is best effort, as there's nothing left to
+/// report an error to.
+///
+/// # Fields
+/// - `original_size`: The terminal size before the game resized it.
+///
+/// # Methods
+/// ## `enter`
+/// Puts the terminal in raw mode, resizes it to fit the board, clears it and hides the cursor.
+///
+/// ### Parameters
+/// - `cols`: The number of columns the game needs.
+/// - `rows`: The number of rows the game needs.
+///
+/// ### Returns
+/// The `TerminalGuard`, or the error of the terminal operation that failed, in which case whatever
+/// was already changed is restored.
+///
+/// ## `drop`
+/// Restores the original size of the terminal, clears it, shows the cursor, resets the colors and
+/// leaves raw mode.
+///
+/// # Example
+/// ```no_run
+/// # use snake_the_sequel::terminal::TerminalGuard;
+/// let terminal = TerminalGuard::enter(33, 13)?;
+/// // draw the game
+/// drop(terminal);
+/// # Ok::<(), snake_the_sequel::error::GameError>(())
+/// ```
+pub struct TerminalGuard {
+    original_size: (u16, u16)
+}
+
+impl TerminalGuard {
+    pub fn enter(cols: u16, rows: u16) -> Result<Self, GameError> {
+        let original_size = size()?;
+        enable_raw_mode()?;
+
+        // from here on, dropping the guard undoes the changes below if one of them fails
+        let guard = Self { original_size };
+        stdout()
+            .execute(SetSize(cols, rows))?
+            .execute(Clear(ClearType::All))?
+            .execute(Hide)?;
+
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let (cols, rows) = self.original_size;
+        let _ = stdout()
+            .execute(SetSize(cols, rows))
+            .and_then(|stdout| stdout.execute(Clear(ClearType::All)))
+            .and_then(|stdout| stdout.execute(Show))
+            .and_then(|stdout| stdout.execute(ResetColor));
+        let _ = disable_raw_mode();
+    }
+}
@@ -0,0 +1,172 @@
+use crossterm::style::Color;
+
+const CLASSIC: Theme = Theme {
+    snake: [Color::Green, Color::Cyan, Color::Yellow],
+    player_two: Color::Magenta,
+    food: Color::White,
+    bonus_food: Color::Blue,
+    shrink_food: Color::Red,
+    border: Color::DarkGrey,
+    glyphs: Glyphs::UNICODE
+};
+const NEON: Theme = Theme {
+    snake: [Color::Magenta, Color::Cyan, Color::Green],
+    player_two: Color::Yellow,
+    food: Color::White,
+    bonus_food: Color::Cyan,
+    shrink_food: Color::Red,
+    border: Color::Magenta,
+    glyphs: Glyphs::UNICODE
+};
+const MONOCHROME: Theme = Theme {
+    snake: [Color::White; 3],
+    player_two: Color::Grey,
+    food: Color::White,
+    bonus_food: Color::White,
+    shrink_food: Color::Grey,
+    border: Color::Grey,
+    glyphs: Glyphs::UNICODE
+};
+const NAMES: [(&str, Theme); 3] = [("classic", CLASSIC), ("neon", NEON), ("monochrome", MONOCHROME)];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents the characters the board is drawn with.
+///
+/// # Fields
+/// - `head`: The head of a snake.
+/// - `vertical`: A straight vertical segment of a snake, and the sides of a box.
+/// - `horizontal`: A straight horizontal segment of a snake, and the top and bottom of a box.
+/// - `top_left`: A segment turning from the right to below, and the top left corner of a box.
+/// - `top_right`: A segment turning from the left to below, and the top right corner of a box.
+/// - `bottom_left`: A segment turning from the right to above, and the bottom left corner of a box.
+/// - `bottom_right`: A segment turning from the left to above, and the bottom right corner of a box.
+/// - `food`: The normal food.
+/// - `bonus_food`: The bonus food.
+/// - `shrink_food`: The shrink food.
+/// - `border`: The borders of the board.
+/// - `dot`: The small dot of the hints, the trail, the particles and the stars.
+/// - `bug`: The frames of the bug's wriggle.
+/// - `arrows`: The arrows pointing up, right, down and left, which show the shuffled controls.
+pub struct Glyphs {
+    pub head: char,
+    pub vertical: char,
+    pub horizontal: char,
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub food: char,
+    pub bonus_food: char,
+    pub shrink_food: char,
+    pub border: char,
+    pub dot: char,
+    pub bug: [char; 2],
+    pub arrows: [char; 4]
+}
+
+impl Glyphs {
+    pub const UNICODE: Glyphs = Glyphs {
+        head: 'O',
+        vertical: '║',
+        horizontal: '═',
+        top_left: '╔',
+        top_right: '╗',
+        bottom_left: '╚',
+        bottom_right: '╝',
+        food: '•',
+        bonus_food: '◆',
+        shrink_food: '○',
+        border: '#',
+        dot: '·',
+        bug: ['¤', '×'],
+        arrows: ['↑', '→', '↓', '←']
+    };
+
+    // for terminals and fonts without the box-drawing characters
+    pub const ASCII: Glyphs = Glyphs {
+        head: 'O',
+        vertical: '|',
+        horizontal: '-',
+        top_left: '+',
+        top_right: '+',
+        bottom_left: '+',
+        bottom_right: '+',
+        food: '*',
+        bonus_food: '%',
+        shrink_food: 'o',
+        border: '#',
+        dot: '.',
+        bug: ['x', 'X'],
+        arrows: ['^', '>', 'v', '<']
+    };
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Represents the look of the board: the colors of the snakes, the food and the borders, and the
+/// characters everything is drawn with.
+///
+/// The colors chosen in the config (see `Colors`) still win over the theme's.
+///
+/// # Fields
+/// - `snake`: The colors of the first snake, which changes between them as the speed goes up.
+/// - `player_two`: The color of the second snake.
+/// - `food`: The color of fresh normal food, which fades to grey as it loses value.
+/// - `bonus_food`: The color of the bonus food.
+/// - `shrink_food`: The color of the shrink food.
+/// - `border`: The color of the borders and obstacles.
+/// - `glyphs`: The characters the board is drawn with.
+///
+/// # Methods
+/// ## `from_name`
+/// Looks up a theme by its name (`classic`, `neon` or `monochrome`).
+///
+/// ### Returns
+/// `Some(Theme)` for a known name, otherwise `None`.
+///
+/// ## `get_name`
+/// Looks up the name of a theme, whichever glyphs it's drawn with.
+///
+/// ### Returns
+/// The name of a built-in theme, or `None` for a theme of its own.
+///
+/// ## `default`
+/// Returns the classic theme.
+///
+/// # Example
+/// ```rust
+/// # use snake_the_sequel::config::Config;
+/// # use snake_the_sequel::game::Game;
+/// # use snake_the_sequel::theme::{Glyphs, Theme};
+/// # let mut game = Game::new(Vec::new(), &Config::default());
+/// let mut theme = Theme::from_name("neon").unwrap();
+/// theme.glyphs = Glyphs::ASCII;
+/// game.set_theme(theme);
+/// ```
+pub struct Theme {
+    pub snake: [Color; 3],
+    pub player_two: Color,
+    pub food: Color,
+    pub bonus_food: Color,
+    pub shrink_food: Color,
+    pub border: Color,
+    pub glyphs: Glyphs
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [CLASSIC, NEON, MONOCHROME];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        NAMES.iter().find(|(known, _)| *known == name).map(|(_, theme)| *theme)
+    }
+
+    pub fn get_name(&self) -> Option<&'static str> {
+        let colors = Self { glyphs: Glyphs::UNICODE, ..*self };
+        NAMES.iter().find(|(_, theme)| *theme == colors).map(|(name, _)| *name)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        CLASSIC
+    }
+}